@@ -0,0 +1,280 @@
+use std::{
+    collections::HashSet,
+    fmt,
+};
+
+use anyhow::anyhow;
+use euph::{
+    env::{
+        EuEnv,
+        EuEnvCtx,
+        EuHook,
+        EuScope,
+    },
+    types::{
+        EuRes,
+        EuSyn,
+        EuType,
+    },
+};
+use itertools::Itertools;
+use rustyline::{
+    DefaultEditor,
+    error::ReadlineError,
+};
+
+const HELP: &str = "\
+s, step            evaluate the next token
+n, next            step over word calls and nested evaluation
+c, continue        run until the next breakpoint
+b, break [WORD]    break before WORD is called
+b, break LINE[:COL]
+                   break at a position in the program
+b, break           list breakpoints
+d, delete N        delete breakpoint N
+st, stack          print the stack
+sc, scope [NAME]   print program bindings, or those containing NAME
+bt, where          print the call stack
+q, quit            abort the program
+";
+
+/// Interactive step debugger for `--debugger`, installed as an [`EuHook`].
+pub struct Debugger {
+    rl: DefaultEditor,
+    /// Source id of the program, for positional breakpoints.
+    src: u32,
+    /// Prelude bindings, hidden from `scope`.
+    prelude: HashSet<String>,
+    mode: Mode,
+    breaks: Vec<Break>,
+    /// Line of the last token seen, so line breakpoints fire once per visit.
+    line: Option<usize>,
+    /// Command repeated by an empty line.
+    last: String,
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Step,
+    Over(usize),
+    Continue,
+}
+
+enum Break {
+    Word(String),
+    Pos(usize, Option<usize>),
+}
+
+impl fmt::Display for Break {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(w) => write!(f, "word `{w}`"),
+            Self::Pos(line, None) => write!(f, "line {line}"),
+            Self::Pos(line, Some(col)) => write!(f, "line {line}, column {col}"),
+        }
+    }
+}
+
+impl Break {
+    fn parse(s: &str) -> Self {
+        let pos = match s.split_once(':') {
+            Some((l, c)) => l.parse().ok().zip(c.parse().ok().map(Some)),
+            None => s.parse().ok().map(|l| (l, None)),
+        };
+        match pos {
+            Some((line, col)) => Self::Pos(line, col),
+            None => Self::Word(s.into()),
+        }
+    }
+}
+
+impl Debugger {
+    /// Prepares to debug `code`, evaluated in `scope`.
+    pub fn new(ctx: &EuEnvCtx, code: &str, scope: &EuScope) -> anyhow::Result<Self> {
+        Ok(Self {
+            rl: DefaultEditor::new()?,
+            src: ctx.add_source(code),
+            prelude: scope.keys().map(ToString::to_string).collect(),
+            mode: Mode::Step,
+            breaks: Vec::new(),
+            line: None,
+            last: "step".into(),
+        })
+    }
+
+    fn hit(&mut self, env: &EuEnv<'_>, t: &EuSyn<'_>) -> bool {
+        let word = match t {
+            EuSyn::Raw(EuType::Word(w, _), _) => Some(&**w),
+            _ => None,
+        };
+        let sp = t.span();
+        let loc = (sp.src == self.src && self.breaks.iter().any(|b| matches!(b, Break::Pos(..))))
+            .then(|| env.ctx.locate(sp))
+            .flatten();
+        let entered = loc.as_ref().is_some_and(|l| self.line != Some(l.line));
+        if loc.is_some() {
+            self.line = loc.as_ref().map(|l| l.line);
+        }
+        self.breaks.iter().any(|b| match (b, &loc) {
+            (Break::Word(w), _) => word == Some(w),
+            (Break::Pos(line, None), Some(l)) => entered && l.line == *line,
+            (Break::Pos(line, Some(col)), Some(l)) => l.line == *line && l.col == *col,
+            _ => false,
+        })
+    }
+
+    fn show(env: &EuEnv<'_>, t: &EuSyn<'_>) {
+        match env.ctx.locate(t.span()) {
+            Some(loc) => eprint!("> {t:?} {}", loc.snippet()),
+            None => eprintln!("> {t:?}"),
+        }
+    }
+
+    fn command(&mut self, env: &EuEnv<'_>, line: &str, depth: usize) -> EuRes<Option<Mode>> {
+        let mut args = line.split_whitespace();
+        match args.next().unwrap_or_default() {
+            "s" | "step" => return Ok(Some(Mode::Step)),
+            "n" | "next" => return Ok(Some(Mode::Over(depth))),
+            "c" | "continue" => return Ok(Some(Mode::Continue)),
+            "b" | "break" => match args.next() {
+                Some(s) => {
+                    let b = Break::parse(s);
+                    eprintln!("breakpoint {} at {b}", self.breaks.len());
+                    self.breaks.push(b);
+                }
+                None if self.breaks.is_empty() => eprintln!("no breakpoints"),
+                None => {
+                    for (i, b) in self.breaks.iter().enumerate() {
+                        eprintln!("{i}: {b}");
+                    }
+                }
+            },
+            "d" | "delete" => match args.next().and_then(|s| s.parse().ok()) {
+                Some(i) if i < self.breaks.len() => {
+                    eprintln!("deleted {}", self.breaks.remove(i));
+                }
+                _ => eprintln!("no such breakpoint"),
+            },
+            "st" | "stack" => {
+                for (i, t) in env.stack.iter().enumerate().rev() {
+                    eprintln!("{i}: {t:?}");
+                }
+            }
+            "sc" | "scope" => {
+                let name = args.next().unwrap_or_default();
+                env.scope
+                    .iter()
+                    .filter(|(k, _)| {
+                        if name.is_empty() {
+                            !self.prelude.contains(&***k)
+                        } else {
+                            k.contains(name)
+                        }
+                    })
+                    .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                    .for_each(|(k, v)| eprintln!("{k} = {v:?}"));
+            }
+            "bt" | "where" => {
+                for f in env.ctx.trace() {
+                    match f.loc {
+                        Some(loc) => eprintln!("in `{}` at {loc}", f.name),
+                        None => eprintln!("in `{}`", f.name),
+                    }
+                }
+            }
+            "q" | "quit" => return Err(anyhow!("quit debugger").into()),
+            "h" | "help" | "?" => eprint!("{HELP}"),
+            cmd => eprintln!("unknown command `{cmd}`, try `help`"),
+        }
+        Ok(None)
+    }
+}
+
+impl EuHook for Debugger {
+    fn before(&mut self, env: &EuEnv<'_>, t: &EuSyn<'_>, depth: usize) -> EuRes<()> {
+        let hit = self.hit(env, t);
+        let stop = match self.mode {
+            Mode::Step => true,
+            Mode::Over(d) => depth <= d,
+            Mode::Continue => false,
+        };
+        if !(stop || hit) {
+            return Ok(());
+        }
+        Self::show(env, t);
+        loop {
+            let line = match self.rl.readline("dbg> ") {
+                Ok(line) if line.trim().is_empty() => self.last.clone(),
+                Ok(line) => {
+                    _ = self.rl.add_history_entry(&line);
+                    line
+                }
+                Err(ReadlineError::Interrupted | ReadlineError::Eof) => {
+                    return Err(anyhow!("quit debugger").into());
+                }
+                Err(e) => return Err(anyhow!(e).into()),
+            };
+            self.last.clone_from(&line);
+            if let Some(mode) = self.command(env, &line, depth)? {
+                self.mode = mode;
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::AtomicBool,
+    };
+
+    use euph::{
+        EuCaps,
+        EuEnvOpts,
+        parser::{
+            euphrates,
+            input,
+        },
+    };
+    use winnow::Parser;
+
+    use super::*;
+
+    #[test]
+    fn breaks() {
+        let ctx = EuEnvCtx::new(
+            EuEnvOpts {
+                debug: false,
+                no_prelude: true,
+                args: vec![],
+                path: vec![],
+                seed: None,
+                fuel: None,
+                timeout: None,
+                elem_budget: None,
+                caps: EuCaps::Full,
+            },
+            Arc::new(AtomicBool::new(true)),
+            rand::rng(),
+        );
+        let code = "1\n2 \"x\" [3]\n+ dup";
+        let env = EuEnv::apply_str("", &[], EuScope::default(), &ctx).unwrap();
+        let mut dbg = Debugger::new(&ctx, code, &env.scope).unwrap();
+        let ts = euphrates.parse(input(code, dbg.src)).unwrap();
+        let mut hits = |breaks: &[&str]| {
+            dbg.breaks = breaks.iter().copied().map(Break::parse).collect();
+            dbg.line = None;
+            ts.iter()
+                .filter(|t| dbg.hit(&env, t))
+                .map(EuSyn::to_source)
+                .collect_vec()
+        };
+        assert_eq!(hits(&["2"]), ["2"]);
+        assert_eq!(hits(&["2:3"]), ["\"x\""]);
+        assert_eq!(hits(&["2:7"]), ["[3]"]);
+        assert_eq!(hits(&["1", "dup"]), ["1", "dup"]);
+        assert_eq!(hits(&["4"]), Vec::<String>::new());
+    }
+}
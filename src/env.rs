@@ -1,11 +1,26 @@
 use std::{
-    cell::RefCell,
+    cell::{
+        Cell,
+        RefCell,
+    },
+    collections::HashMap,
+    fs,
     hash,
+    io::{
+        self,
+        BufRead,
+        Read,
+        Write,
+    },
     iter::{
         self,
         Peekable,
     },
     mem,
+    path::{
+        Path,
+        PathBuf,
+    },
     rc::Rc,
     sync::{
         Arc,
@@ -14,6 +29,10 @@ use std::{
             Ordering,
         },
     },
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use anyhow::{
@@ -27,25 +46,50 @@ use derive_more::{
 use ecow::EcoVec;
 use hipstr::LocalHipStr;
 use itertools::Itertools;
-use rand::Rng;
+use ordermap::OrderMap;
+use rand::{
+    Rng,
+    SeedableRng,
+    rngs::StdRng,
+};
+use regex::Regex;
 use winnow::Parser;
 
 use crate::{
+    EuCaps,
     EuEnvOpts,
     fns::{
         CORE,
-        bind,
+        bind::{
+            self,
+            EuBindDef,
+        },
+    },
+    parser::{
+        check,
+        euphrates,
+        input,
     },
-    parser::euphrates,
+    profile::EuProfile,
     types::{
         EuBind,
+        EuErr,
+        EuFrame,
         EuIter,
+        EuLoc,
         EuRes,
+        EuSpan,
         EuSyn,
         EuType,
     },
 };
 
+pub const PRELUDE: &str = include_str!("prelude.euph");
+
+/// Compiled patterns kept by [`EuEnvCtx::regex`] before the least recently
+/// used one is dropped.
+pub const REGEX_CACHE: usize = 256;
+
 #[derive(Debug, Display)]
 #[debug("stack: {stack:?}\nscope: {scope:?}")]
 #[display("{stack:?}")]
@@ -60,6 +104,70 @@ pub struct EuEnvCtx {
     pub opts: EuEnvOpts,
     pub interrupt: Arc<AtomicBool>,
     pub rng: RefCell<Box<dyn Rng>>,
+    /// Recently compiled patterns, least recently used first.
+    pub regexes: RefCell<OrderMap<String, Regex>>,
+    pub exit: Cell<Option<i32>>,
+    /// Scopes of evaluated modules by canonical path, so each is loaded once.
+    /// Modules whose scope holds a `Seq` are left out and loaded on every import.
+    pub modules: RefCell<HashMap<PathBuf, EuScope<'static>>>,
+    pub loading: RefCell<Vec<PathBuf>>,
+    /// Registered sources, where source `n` is at index `n - 1`.
+    pub sources: RefCell<Vec<Rc<str>>>,
+    /// Ids of [`Self::sources`] by text, so repeated sources share one.
+    source_ids: RefCell<HashMap<Rc<str>, u32>>,
+    pub calls: RefCell<Vec<(LocalHipStr<'static>, EuSpan)>>,
+    pub span: Cell<EuSpan>,
+    /// Steps left before evaluation fails, if [`EuEnvOpts::fuel`] is set.
+    pub fuel: Cell<Option<u64>>,
+    /// When evaluation fails, if [`EuEnvOpts::timeout`] is set.
+    pub deadline: Cell<Option<Instant>>,
+    /// Collection elements left to build before evaluation fails, if
+    /// [`EuEnvOpts::elem_budget`] is set.
+    pub elems: Cell<Option<u64>>,
+    pub natives: RefCell<HashMap<String, Rc<EuNative>>>,
+    pub tags: RefCell<HashMap<String, EuBindDef>>,
+    pub stdin: RefCell<Box<dyn BufRead>>,
+    pub stdout: RefCell<Box<dyn Write>>,
+    pub stderr: RefCell<Box<dyn Write>>,
+    /// Where `--debug` traces evaluation.
+    pub trace: RefCell<Box<dyn Write>>,
+    /// Called before each evaluated token; see [`Self::with_hook`].
+    pub hook: RefCell<Option<Box<dyn EuHook>>>,
+    /// Number of [`EuEnv::eval`] loops currently running.
+    pub depth: Cell<usize>,
+    /// Word timings, collected while set; see [`Self::with_profile`].
+    pub profile: RefCell<Option<EuProfile>>,
+}
+
+/// Observes evaluation, e.g. to implement a debugger. Installed with
+/// [`EuEnvCtx::with_hook`].
+pub trait EuHook {
+    /// Called before each token in `env`'s queue is evaluated. `depth` grows
+    /// with every nested evaluation and word call, so comparing it across
+    /// calls tells whether `t` is inside the previous token. Code the hook
+    /// evaluates itself is not observed.
+    fn before(&mut self, env: &EuEnv<'_>, t: &EuSyn<'_>, depth: usize) -> EuRes<()>;
+}
+
+pub type EuNativeFn = dyn for<'eu> Fn(&mut EuEnv<'eu>) -> EuRes<()>;
+
+/// A word provided by the application embedding euphrates, registered with
+/// [`EuEnvCtx::register`].
+pub struct EuNative {
+    /// Number of stack items the word needs, checked before it is called.
+    pub arity: usize,
+    pub doc: String,
+    pub f: Box<EuNativeFn>,
+}
+
+/// Keeps a call on [`EuEnvCtx::calls`] while its tokens are being evaluated.
+#[derive(Clone)]
+struct EuCallIter<'eu, I> {
+    it: I,
+    ctx: &'eu EuEnvCtx,
+    call: Option<(LocalHipStr<'static>, EuSpan)>,
+    depth: Option<usize>,
+    frame: Option<usize>,
 }
 
 pub type EuScope<'eu> =
@@ -138,44 +246,89 @@ impl<'eu> EuEnv<'eu> {
         scope: EuScope<'eu>,
         ctx: &'eu EuEnvCtx,
     ) -> EuRes<EuEnv<'eu>> {
-        Self::apply(
-            euphrates.parse(s).map_err(|e| anyhow!(e.to_string()))?,
-            args,
-            scope,
-            ctx,
-        )
+        let src = ctx.add_source(s);
+        let ts = euphrates.parse(input(s, src)).map_err(|e| {
+            let diags = check(s, src);
+            let Some(d) = diags.first() else {
+                return anyhow!(e.to_string()).into();
+            };
+            // The first diagnostic is located by the snippet below.
+            EuErr::from(anyhow!(
+                iter::once(d.to_string())
+                    .chain(diags[1..].iter().map(|d| match ctx.locate(d.span) {
+                        Some(loc) => format!("{d} at {loc}"),
+                        None => d.to_string(),
+                    }))
+                    .join("\n")
+            ))
+            .at(|| ctx.locate(d.span))
+        })?;
+        Self::apply(ts, args, scope, ctx)
+    }
+
+    /// Evaluates the prelude, unless disabled. The prelude spends no fuel.
+    pub fn prelude(ctx: &'eu EuEnvCtx) -> EuRes<EuScope<'eu>> {
+        if ctx.opts.no_prelude {
+            return Ok(EuScope::default());
+        }
+        let fuel = ctx.fuel.take();
+        let res = Self::apply_str(PRELUDE, &[], EuScope::default(), ctx);
+        ctx.fuel.set(fuel);
+        Ok(res.context("in prelude")?.scope)
     }
 
     pub fn eval(&mut self) -> EuRes<()> {
+        let depth = &self.ctx.depth;
+        depth.set(depth.get() + 1);
+        let res = self.eval_queue();
+        depth.set(depth.get() - 1);
+        res
+    }
+
+    fn eval_queue(&mut self) -> EuRes<()> {
         while let Some(t) = self.queue.next() {
             #[cfg(not(target_arch = "wasm32"))]
             if !self.ctx.interrupt.load(Ordering::SeqCst) {
                 return Err(anyhow!("interrupted").into());
             }
+            self.ctx.burn()?;
+            if let Some(code) = self.ctx.exit.get() {
+                return Err(anyhow!("exit {code}").into());
+            }
+            // Taken out for the call, so the hook may evaluate code itself.
+            if let Some(mut hook) = self.ctx.hook.take() {
+                let depth = self.ctx.depth.get() + self.ctx.calls.borrow().len();
+                let res = hook.before(self, &t, depth);
+                self.ctx.hook.replace(Some(hook));
+                res?;
+            }
             if self.ctx.opts.debug {
-                println!("{t:?}\n>>>");
+                writeln!(self.ctx.trace.borrow_mut(), "{t:?}\n>>>").map_err(|e| anyhow!(e))?;
             }
             self.eval_syn(t)?;
             if self.ctx.opts.debug {
-                println!("{self:?}\n<<<\n");
+                writeln!(self.ctx.trace.borrow_mut(), "{self:?}\n<<<\n").map_err(|e| anyhow!(e))?;
             }
         }
         Ok(())
     }
 
     fn eval_syn(&mut self, t: EuSyn<'eu>) -> EuRes<()> {
+        let ctx = self.ctx;
+        let sp = t.span();
+        ctx.span.set(sp);
         match t {
-            EuSyn::Raw(t) => self.eval_type(t),
-            EuSyn::Var(s) => self.eval_var(&s),
-            EuSyn::Move(s) => self.eval_move(&s),
-            EuSyn::Get(k) => self.eval_get(&k),
-            EuSyn::Vec(ts) => {
+            EuSyn::Raw(t, _) => self.eval_type(t),
+            EuSyn::Var(s, _) => self.eval_var(&s),
+            EuSyn::Move(s, _) => self.eval_move(&s),
+            EuSyn::Get(k, _) => self.eval_get(&k),
+            EuSyn::Vec(ts, _) => {
                 self.push(EuType::vec(
                     Self::apply(ts, &[], self.scope.clone(), self.ctx)?.stack,
                 ));
                 Ok(())
             }
-            EuSyn::Map(ts) => {
+            EuSyn::Map(ts, _) => {
                 self.push(EuType::Map(Rc::new(
                     Self::apply(ts, &[], self.scope.clone(), self.ctx)?
                         .stack
@@ -185,13 +338,14 @@ impl<'eu> EuEnv<'eu> {
                 )));
                 Ok(())
             }
-            EuSyn::Bind(bs) => self.bind_args(&bs),
+            EuSyn::Bind(bs, _) => self.bind_args(&bs),
         }
+        .map_err(|e| e.at(|| ctx.locate(sp)).traced(|| ctx.trace()))
     }
 
     fn eval_type(&mut self, t: EuType<'eu>) -> EuRes<()> {
         match t {
-            EuType::Word(w) => self.eval_word(&w),
+            EuType::Word(w, _) => self.eval_word(&w),
             EuType::Res(Err(e)) => Err(anyhow!(e.to_string()).into()),
             _ => {
                 self.push(t);
@@ -203,14 +357,22 @@ impl<'eu> EuEnv<'eu> {
     fn eval_word(&mut self, w: &str) -> EuRes<()> {
         if let Some(v) = self.scope.get(w) {
             if let EuType::Expr(ts) = v {
-                self.eval_iter(ts.clone())
+                self.eval_call(w, ts.clone())
             } else {
                 self.push(v.clone());
                 Ok(())
             }
+        } else if let Some(n) = self.ctx.native(w) {
+            let frame = self.ctx.prof_enter(w);
+            let res = self.check_nargs(n.arity).and_then(|()| (n.f)(self));
+            self.ctx.prof_exit(frame);
+            res.with_context(|| format!("`{w}` failed"))
+                .map_err(Into::into)
         } else if let Some(f) = CORE.get(w) {
-            f(self)
-                .with_context(|| format!("`{w}` failed"))
+            let frame = self.ctx.prof_enter(w);
+            let res = f(self);
+            self.ctx.prof_exit(frame);
+            res.with_context(|| format!("`{w}` failed"))
                 .map_err(Into::into)
         } else {
             Err(anyhow!("unknown word `{w}`").into())
@@ -241,7 +403,23 @@ impl<'eu> EuEnv<'eu> {
             .get(&EuType::str(w))?
             .with_context(|| format!("missing key `{w}`"))?
             .to_expr()?;
-        self.eval_iter(ts)
+        self.eval_call(&format!(".{w}"), ts)
+    }
+
+    /// Like [`Self::eval_iter`], but records `name` on the call stack while
+    /// `ts` is being evaluated. Tail calls replace their caller's frame.
+    pub fn eval_call<T>(&mut self, name: &str, ts: T) -> EuRes<()>
+    where
+        T: IntoIterator<Item = EuSyn<'eu>>,
+        T::IntoIter: 'eu,
+    {
+        self.eval_iter(EuCallIter {
+            it: ts.into_iter(),
+            ctx: self.ctx,
+            call: Some((LocalHipStr::from(name).into_owned(), self.ctx.span.get())),
+            depth: None,
+            frame: None,
+        })
     }
 
     pub fn eval_iter<T>(&mut self, ts: T) -> EuRes<()>
@@ -304,16 +482,18 @@ impl<'eu> EuEnv<'eu> {
         }
 
         match b {
-            EuBind::Word(w) => {
+            EuBind::Word(w, _) => {
                 self.scope.insert(w.clone(), t);
             }
 
-            EuBind::Tag(w, bs) => {
-                if let Some(f) = bind::BIND.get(w) {
-                    (f.bind)(self, bs, t).with_context(|| format!("in `${w}`"))?;
+            EuBind::Tag(w, bs, sp) => {
+                let ctx = self.ctx;
+                if let Some(f) = ctx.tag(w) {
+                    (f.bind)(self, bs, t).with_context(|| format!("in `${w}`"))
                 } else {
-                    return Err(anyhow!("unknown tag `${w}`").into());
+                    Err(anyhow!("unknown tag `${w}`"))
                 }
+                .map_err(|e| EuErr::from(e).at(|| ctx.locate(*sp)))?;
             }
 
             EuBind::Union(bs) => {
@@ -394,6 +574,41 @@ impl<'eu> EuEnv<'eu> {
         }
     }
 
+    pub fn import(&self, name: &str) -> EuRes<EuScope<'eu>> {
+        let path = self.ctx.resolve(name)?;
+        if let Some(scope) = self.ctx.modules.borrow().get(&path) {
+            return own_scope(scope.clone());
+        }
+        if self.ctx.loading.borrow().contains(&path) {
+            return Err(anyhow!(
+                "import cycle `{}`",
+                self.ctx
+                    .loading
+                    .borrow()
+                    .iter()
+                    .chain([&path])
+                    .map(|p| p.display())
+                    .join(" -> ")
+            )
+            .into());
+        }
+        let src = fs::read_to_string(&path).map_err(|e| anyhow!(e))?;
+
+        self.ctx.loading.borrow_mut().push(path.clone());
+        let res =
+            Self::prelude(self.ctx).and_then(|scope| Self::apply_str(&src, &[], scope, self.ctx));
+        self.ctx.loading.borrow_mut().pop();
+
+        let scope = res
+            .with_context(|| format!("in module `{}`", path.display()))?
+            .scope;
+        // Modules binding a `Seq` are evaluated again on each import instead.
+        if let Ok(cached) = own_scope(scope.clone()) {
+            self.ctx.modules.borrow_mut().insert(path, cached);
+        }
+        Ok(scope)
+    }
+
     #[inline]
     pub fn clear_queue(&mut self) {
         let queue: EuIter<'_> = Box::new(iter::empty());
@@ -402,11 +617,836 @@ impl<'eu> EuEnv<'eu> {
 }
 
 impl EuEnvCtx {
+    /// Uses `rng` unless [`EuEnvOpts::seed`] is set, in which case a
+    /// [`StdRng`] is seeded from it. Limits start counting immediately; see
+    /// [`Self::reset_limits`].
     pub fn new<R: Rng + 'static>(opts: EuEnvOpts, interrupt: Arc<AtomicBool>, rng: R) -> Self {
-        EuEnvCtx {
+        let rng: Box<dyn Rng> = match opts.seed {
+            Some(n) => Box::new(StdRng::seed_from_u64(n)),
+            None => Box::new(rng),
+        };
+        let ctx = EuEnvCtx {
             opts,
             interrupt,
-            rng: RefCell::new(Box::new(rng)),
+            rng: RefCell::new(rng),
+            regexes: RefCell::new(OrderMap::new()),
+            exit: Cell::new(None),
+            modules: RefCell::new(HashMap::new()),
+            loading: RefCell::new(Vec::new()),
+            sources: RefCell::new(Vec::new()),
+            source_ids: RefCell::new(HashMap::new()),
+            calls: RefCell::new(Vec::new()),
+            span: Cell::new(EuSpan::default()),
+            fuel: Cell::new(None),
+            deadline: Cell::new(None),
+            elems: Cell::new(None),
+            natives: RefCell::new(HashMap::new()),
+            tags: RefCell::new(HashMap::new()),
+            stdin: RefCell::new(Box::new(EuStdin(None))),
+            stdout: RefCell::new(Box::new(io::stdout())),
+            stderr: RefCell::new(Box::new(io::stderr())),
+            trace: RefCell::new(Box::new(io::stdout())),
+            hook: RefCell::new(None),
+            depth: Cell::new(0),
+            profile: RefCell::new(None),
+        };
+        ctx.reset_limits();
+        ctx
+    }
+
+    /// Refills the fuel and element budget and restarts the timeout clock, so
+    /// that limits apply afresh to the code evaluated next, e.g. after the
+    /// prelude or for each REPL line.
+    pub fn reset_limits(&self) {
+        self.fuel.set(self.opts.fuel);
+        self.elems.set(self.opts.elem_budget);
+        self.deadline.set(
+            self.opts
+                .timeout
+                .map(|ms| Instant::now() + Duration::from_millis(ms)),
+        );
+    }
+
+    #[must_use]
+    pub fn with_stdin(self, r: impl BufRead + 'static) -> Self {
+        *self.stdin.borrow_mut() = Box::new(r);
+        self
+    }
+
+    #[must_use]
+    pub fn with_stdout(self, w: impl Write + 'static) -> Self {
+        *self.stdout.borrow_mut() = Box::new(w);
+        self
+    }
+
+    #[must_use]
+    pub fn with_stderr(self, w: impl Write + 'static) -> Self {
+        *self.stderr.borrow_mut() = Box::new(w);
+        self
+    }
+
+    #[must_use]
+    pub fn with_trace(self, w: impl Write + 'static) -> Self {
+        *self.trace.borrow_mut() = Box::new(w);
+        self
+    }
+
+    /// Records timings of word calls in [`Self::profile`].
+    #[must_use]
+    pub fn with_profile(self) -> Self {
+        *self.profile.borrow_mut() = Some(EuProfile::default());
+        self
+    }
+
+    #[inline]
+    fn prof_enter(&self, name: &str) -> Option<usize> {
+        self.profile.borrow_mut().as_mut().map(|p| p.enter(name))
+    }
+
+    #[inline]
+    fn prof_exit(&self, frame: Option<usize>) {
+        if let (Some(p), Some(f)) = (self.profile.borrow_mut().as_mut(), frame) {
+            p.exit(f);
+        }
+    }
+
+    #[must_use]
+    pub fn with_hook(self, hook: impl EuHook + 'static) -> Self {
+        *self.hook.borrow_mut() = Some(Box::new(hook));
+        self
+    }
+
+    /// Registers a native word, looked up after the scope and before the
+    /// builtins. Fails if `name` is a builtin; see [`Self::register_override`].
+    pub fn register<F>(&self, name: &str, arity: usize, doc: &str, f: F) -> EuRes<()>
+    where
+        F: for<'eu> Fn(&mut EuEnv<'eu>) -> EuRes<()> + 'static,
+    {
+        if CORE.contains_key(name) {
+            return Err(anyhow!("`{name}` is a builtin").into());
+        }
+        self.register_override(name, arity, doc, f);
+        Ok(())
+    }
+
+    /// Registers a native word, shadowing any builtin of the same name.
+    pub fn register_override<F>(&self, name: &str, arity: usize, doc: &str, f: F)
+    where
+        F: for<'eu> Fn(&mut EuEnv<'eu>) -> EuRes<()> + 'static,
+    {
+        self.natives.borrow_mut().insert(
+            name.into(),
+            Rc::new(EuNative {
+                arity,
+                doc: doc.into(),
+                f: Box::new(f),
+            }),
+        );
+    }
+
+    /// Registers a bind tag `$name(...)`. Fails if `name` is a builtin tag;
+    /// see [`Self::register_tag_override`].
+    pub fn register_tag(&self, name: &str, def: EuBindDef) -> EuRes<()> {
+        if bind::BIND.contains_key(name) {
+            return Err(anyhow!("`${name}` is a builtin tag").into());
+        }
+        self.register_tag_override(name, def);
+        Ok(())
+    }
+
+    /// Registers a bind tag `$name(...)`, shadowing any builtin of the same
+    /// name.
+    pub fn register_tag_override(&self, name: &str, def: EuBindDef) {
+        self.tags.borrow_mut().insert(name.into(), def);
+    }
+
+    #[must_use]
+    pub fn native(&self, name: &str) -> Option<Rc<EuNative>> {
+        self.natives.borrow().get(name).cloned()
+    }
+
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<EuBindDef> {
+        self.tags
+            .borrow()
+            .get(name)
+            .or_else(|| bind::BIND.get(name))
+            .copied()
+    }
+
+    pub fn regex(&self, pat: &str) -> EuRes<Regex> {
+        let mut regexes = self.regexes.borrow_mut();
+        if let Some(i) = regexes.get_index_of(pat) {
+            let last = regexes.len() - 1;
+            regexes.move_index(i, last);
+            return Ok(regexes[last].clone());
+        }
+        let re = Regex::new(pat).map_err(|e| anyhow!(e))?;
+        if regexes.len() >= REGEX_CACHE {
+            regexes.remove_index(0);
+        }
+        regexes.insert(pat.into(), re.clone());
+        Ok(re)
+    }
+
+    /// Fails unless [`EuEnvOpts::caps`] grants at least `caps`.
+    pub fn require(&self, caps: EuCaps) -> EuRes<()> {
+        let granted = match (self.opts.caps, caps) {
+            (EuCaps::Full, _)
+            | (EuCaps::Stdio, EuCaps::Stdio | EuCaps::Stdout | EuCaps::Pure)
+            | (EuCaps::Stdout, EuCaps::Stdout | EuCaps::Pure)
+            | (EuCaps::Pure, EuCaps::Pure) => true,
+            (EuCaps::Stdio, EuCaps::Full)
+            | (EuCaps::Stdout, EuCaps::Stdio | EuCaps::Full)
+            | (EuCaps::Pure, EuCaps::Stdout | EuCaps::Stdio | EuCaps::Full) => false,
+        };
+        if granted {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "permission denied: needs {} access, but only {} is granted",
+                caps_name(caps),
+                caps_name(self.opts.caps)
+            )
+            .into())
+        }
+    }
+
+    /// Spends one step of fuel, failing once it has run out or the deadline
+    /// has passed.
+    #[inline]
+    pub fn burn(&self) -> EuRes<()> {
+        if let Some(d) = self.deadline.get()
+            && Instant::now() >= d
+        {
+            return Err(anyhow!(
+                "timed out after {}ms",
+                self.opts.timeout.unwrap_or_default()
+            )
+            .into());
+        }
+        match self.fuel.get() {
+            Some(0) => Err(anyhow!("fuel exhausted").into()),
+            Some(n) => {
+                self.fuel.set(Some(n - 1));
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Spends `n` elements of the budget, failing once it runs out.
+    pub fn charge_elems(&self, n: usize) -> EuRes<()> {
+        match self.elems.get() {
+            Some(left) => match left.checked_sub(u64::try_from(n).unwrap_or(u64::MAX)) {
+                Some(left) => {
+                    self.elems.set(Some(left));
+                    Ok(())
+                }
+                None => Err(anyhow!("element budget exhausted").into()),
+            },
+            None => Ok(()),
+        }
+    }
+
+    #[must_use]
+    pub fn trace(&self) -> EcoVec<EuFrame> {
+        self.calls
+            .borrow()
+            .iter()
+            .rev()
+            .map(|(name, sp)| EuFrame {
+                name: name.to_string(),
+                loc: self.locate(*sp),
+            })
+            .collect()
+    }
+
+    /// Registers `s` for error locations, returning its id. Registering the
+    /// same text again, e.g. the prelude, returns the existing id, which is
+    /// also the one [`EuEnv::apply_str`] gives spans of `s`.
+    pub fn add_source(&self, s: &str) -> u32 {
+        let mut ids = self.source_ids.borrow_mut();
+        if let Some(&id) = ids.get(s) {
+            return id;
+        }
+        let mut sources = self.sources.borrow_mut();
+        let s: Rc<str> = s.into();
+        sources.push(s.clone());
+        #[expect(clippy::missing_panics_doc, reason = "sources fit in u32")]
+        let id = sources.len().try_into().unwrap();
+        ids.insert(s, id);
+        id
+    }
+
+    #[must_use]
+    pub fn locate(&self, sp: EuSpan) -> Option<EuLoc> {
+        let i = usize::try_from(sp.src).ok()?.checked_sub(1)?;
+        self.sources.borrow().get(i).map(|s| EuLoc::new(s, sp))
+    }
+
+    pub fn resolve(&self, name: &str) -> EuRes<PathBuf> {
+        self.require(EuCaps::Full)?;
+        let mut file = PathBuf::from(name);
+        if file.extension().is_none() {
+            file.set_extension("euph");
+        }
+        let dirs = self
+            .loading
+            .borrow()
+            .last()
+            .and_then(|p| p.parent())
+            .map(Path::to_path_buf)
+            .into_iter()
+            .chain(self.opts.path.iter().map(PathBuf::from))
+            .chain([PathBuf::new()])
+            .collect_vec();
+        dirs.into_iter()
+            .map(|d| d.join(&file))
+            .find(|p| p.is_file())
+            .with_context(|| format!("module `{name}` not found"))?
+            .canonicalize()
+            .map_err(|e| anyhow!(e).into())
+    }
+}
+
+/// Copies a module's scope out of or into the context that evaluated it.
+fn own_scope<'a>(scope: EuScope<'_>) -> EuRes<EuScope<'a>> {
+    scope
+        .into_iter()
+        .map(|(k, v)| Ok((k.into_owned(), v.into_owned()?)))
+        .collect()
+}
+
+fn caps_name(caps: EuCaps) -> &'static str {
+    match caps {
+        EuCaps::Pure => "pure",
+        EuCaps::Stdout => "stdout",
+        EuCaps::Stdio => "stdio",
+        EuCaps::Full => "full",
+    }
+}
+
+impl<I> EuCallIter<'_, I> {
+    fn pop(&mut self) {
+        if let Some(d) = self.depth.take() {
+            self.ctx.calls.borrow_mut().truncate(d);
         }
+        self.ctx.prof_exit(self.frame.take());
+    }
+}
+
+impl<'eu, I: Iterator<Item = EuSyn<'eu>>> Iterator for EuCallIter<'eu, I> {
+    type Item = EuSyn<'eu>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(call) = self.call.take() {
+            self.frame = self.ctx.prof_enter(&call.0);
+            let mut calls = self.ctx.calls.borrow_mut();
+            self.depth = Some(calls.len());
+            calls.push(call);
+        }
+        let t = self.it.next();
+        if t.is_none() {
+            self.pop();
+        }
+        t
+    }
+}
+
+impl<I> Drop for EuCallIter<'_, I> {
+    fn drop(&mut self) {
+        self.pop();
+    }
+}
+
+/// The process's stdin, locked on first use so that contexts which never read
+/// it don't block others.
+struct EuStdin(Option<io::StdinLock<'static>>);
+
+impl EuStdin {
+    fn lock(&mut self) -> &mut io::StdinLock<'static> {
+        self.0.get_or_insert_with(|| io::stdin().lock())
+    }
+}
+
+impl Read for EuStdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.lock().read(buf)
+    }
+}
+
+impl BufRead for EuStdin {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.lock().fill_buf()
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.lock().consume(n);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> EuEnvCtx {
+        ctx_with(|_| {})
+    }
+
+    fn ctx_with(f: impl FnOnce(&mut EuEnvOpts)) -> EuEnvCtx {
+        let mut opts = EuEnvOpts {
+            debug: false,
+            no_prelude: true,
+            args: vec![],
+            path: vec![],
+            seed: None,
+            fuel: None,
+            timeout: None,
+            elem_budget: None,
+            caps: EuCaps::Full,
+        };
+        f(&mut opts);
+        EuEnvCtx::new(opts, Arc::new(AtomicBool::new(true)), rand::rng())
+    }
+
+    /// Runs `code` in an empty scope, returning the stack as debug strings.
+    fn run(code: &str, ctx: &EuEnvCtx) -> Result<Vec<String>, String> {
+        EuEnv::apply_str(code, &[], EuScope::default(), ctx)
+            .map(|env| env.stack.iter().map(|t| format!("{t:?}")).collect())
+            .map_err(|e| format!("{:#}", e.0))
+    }
+
+    #[derive(Clone, Default)]
+    struct Buf(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn io() {
+        let (out, err) = (Buf::default(), Buf::default());
+        let ctx = ctx()
+            .with_stdin(io::Cursor::new("line\nrest\n"))
+            .with_stdout(out.clone())
+            .with_stderr(err.clone());
+        EuEnv::apply_str(
+            "readL ? print read ? eprint 1 printL",
+            &[],
+            EuScope::default(),
+            &ctx,
+        )
+        .unwrap();
+        assert_eq!(*out.0.borrow(), b"line\n1\n");
+        assert_eq!(*err.0.borrow(), b"rest\n");
+    }
+
+    #[test]
+    fn sys() {
+        let ctx = ctx_with(|opts| opts.args = vec!["a".into(), "b c".into()]);
+        let run = |code: &str| run(code, &ctx).unwrap();
+        assert_eq!(run("Args"), ["[\"a\" \"b c\"]"]);
+        let (k, v) = std::env::vars()
+            .find(|(k, v)| {
+                (k.clone() + v)
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "/_-.".contains(c))
+            })
+            .unwrap();
+        assert_eq!(run(&format!("Env {k:?} :")), [format!("Some:{v:?}")]);
+        assert_eq!(run("Env \"\" :"), ["None"]);
+    }
+
+    #[test]
+    fn exit() {
+        let (out, err) = (Buf::default(), Buf::default());
+        let ctx = ctx().with_stdout(out.clone()).with_stderr(err.clone());
+        let res = EuEnv::apply_str(
+            "1 eprint 2 eprintL (3 exit) \\[f] [0] (f) map 4 print",
+            &[],
+            EuScope::default(),
+            &ctx,
+        );
+        assert!(res.is_err());
+        assert_eq!(ctx.exit.get(), Some(3));
+        assert_eq!(*err.0.borrow(), b"12\n");
+        assert!(out.0.borrow().is_empty());
+        let ctx = self::ctx();
+        assert!(EuEnv::apply_str("\"x\" exit", &[], EuScope::default(), &ctx).is_err());
+        assert_eq!(ctx.exit.get(), None);
+    }
+
+    #[test]
+    fn files() {
+        let dir = std::env::temp_dir().join(format!("euph-files-{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        let ctx = ctx();
+        let run = |code: &str| {
+            let code = format!("\"{}\" \\[d] {code}", dir.display());
+            let env = EuEnv::apply_str(&code, &[], EuScope::default(), &ctx).unwrap();
+            format!("{:?}", env.stack)
+        };
+        assert_eq!(run("d exists? ?"), "[False]");
+        assert_eq!(run("d mkdir ? exists? ?"), "[True]");
+        assert_eq!(run("\"ab\" d \"/f\" ++ writeF ? readF ?"), "[\"ab\"]");
+        assert_eq!(run("\"c\" d \"/f\" ++ appendF ? readF ?"), "[\"abc\"]");
+        let ls = run("d \"/f\" ++ d \"/g\" ++ mv ? d ls ?");
+        assert!(ls.contains("/g\"") && !ls.contains("/f\""), "{ls}");
+        assert!(run("d \"/f\" ++ readF").starts_with("[Err:"));
+        assert_eq!(run("d \"/g\" ++ rm ? exists? ?"), "[False]");
+        assert_eq!(run("d rm ? exists? ?"), "[False]");
+        assert!(run("d ls").starts_with("[Err:"));
+    }
+
+    #[test]
+    fn prelude() {
+        let ctx = ctx_with(|opts| opts.no_prelude = false);
+        let scope = EuEnv::prelude(&ctx).unwrap();
+        let run = |code| {
+            let env = EuEnv::apply_str(code, &[], scope.clone(), &ctx).unwrap();
+            format!("{:?}", env.stack)
+        };
+        assert_eq!(run("[1 2 3] sum [] sum"), "[6, 0]");
+        assert_eq!(run("[2 3 4] product [] product"), "[24, 1]");
+        assert_eq!(run("[1 2 3] reverse"), "[[3 2 1]]");
+        assert_eq!(run("[3 1 3 2 1] uniq"), "[[3 1 2]]");
+        assert_eq!(run("2 5 range 5 5 range"), "[[2 3 4], []]");
+        assert_eq!(run("[1 2 3 4 5] (2 %) countBy"), "[{1 3, 0 2}]");
+    }
+
+    #[test]
+    fn modules() {
+        let dir = std::env::temp_dir().join(format!("euph-modules-{}", std::process::id()));
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        for (name, src) in [
+            ("m", "\"loaded\" printL (2 *) \\[double]"),
+            ("lib/n", "(1 +) \\[inc]"),
+            ("a", "\"b\" use"),
+            ("b", "\"a\" use"),
+            ("s", "\"seq\" printL SeqN0 \\[nats]"),
+        ] {
+            fs::write(dir.join(name).with_extension("euph"), src).unwrap();
+        }
+        let out = Buf::default();
+        let ctx = ctx_with(|opts| opts.path = vec![dir.join("lib").to_string_lossy().into_owned()])
+            .with_stdout(out.clone());
+        let run = |code: &str| {
+            let code = format!("\"{}/\" \\[d] {code}", dir.display());
+            EuEnv::apply_str(&code, &[], EuScope::default(), &ctx)
+                .map(|env| format!("{:?}", env.stack))
+                .map_err(|e| format!("{:#}", e.0))
+        };
+
+        assert_eq!(
+            run("d \"m\" ++ use 3 double d \"m\" ++ use 4 double"),
+            Ok("[6, 8]".into())
+        );
+        assert_eq!(
+            run("d \"m\" ++ import \"double\" : ?"),
+            Ok("[(2 *)]".into())
+        );
+        assert_eq!(*out.0.borrow(), b"loaded\n");
+        assert_eq!(run("\"n\" use 1 inc"), Ok("[2]".into()));
+        assert!(run("d \"a\" ++ use").unwrap_err().contains("import cycle"));
+        assert_eq!(
+            run("d \"s\" ++ use nats 3 tk >Vec d \"s\" ++ use nats 2 tk >Vec"),
+            Ok("[[0 1 2], [0 1]]".into())
+        );
+        assert_eq!(*out.0.borrow(), b"loaded\nseq\nseq\n");
+        assert!(run("\"m\" use").unwrap_err().contains("not found"));
+        assert_eq!(ctx.modules.borrow().len(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sources() {
+        let ctx = ctx_with(|opts| opts.no_prelude = false);
+        EuEnv::prelude(&ctx).unwrap();
+        EuEnv::prelude(&ctx).unwrap();
+        assert_eq!(ctx.add_source("1"), 2);
+        assert_eq!(ctx.add_source("2"), 3);
+        assert_eq!(ctx.add_source("1"), 2);
+        assert_eq!(ctx.sources.borrow().len(), 3);
+        let err = EuEnv::apply_str("1 x", &[], EuScope::default(), &ctx).unwrap_err();
+        assert_eq!(err.1.unwrap().col, 3);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = EuEnv::apply_str("1 ) 2 ]", &[], EuScope::default(), &ctx()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected `)`\nunexpected `]` at line 1, column 7"
+        );
+        assert_eq!(err.1.unwrap().col, 3);
+        assert_eq!(
+            run("1 ;( 2", &ctx()),
+            Err("unclosed `;(`, expected `;)`".into())
+        );
+    }
+
+    #[test]
+    fn natives() {
+        let ctx = ctx();
+        let calls = Rc::new(Cell::new(0));
+        let c = calls.clone();
+        ctx.register("double", 1, "n -> n*2", move |env| {
+            c.set(c.get() + 1);
+            let a0 = env.arg("a0")?;
+            env.push((a0.clone() + a0)?);
+            Ok(())
+        })
+        .unwrap();
+        assert!(ctx.register("+", 2, "", |_| Ok(())).is_err());
+        ctx.register_override("-", 2, "always 0", |env| {
+            env.arg("a1")?;
+            env.arg("a0")?;
+            env.push(EuType::i32(0));
+            Ok(())
+        });
+
+        let env = EuEnv::apply_str("3 double 5 2 -", &[], EuScope::default(), &ctx).unwrap();
+        assert_eq!(env.stack, [EuType::ibig(6), EuType::i32(0)]);
+        assert_eq!(calls.get(), 1);
+        let env = EuEnv::apply_str("(1) \\[double] double", &[], EuScope::default(), &ctx).unwrap();
+        assert_eq!(env.stack, [EuType::ibig(1)]);
+        assert!(EuEnv::apply_str("double", &[], EuScope::default(), &ctx).is_err());
+        assert_eq!(ctx.native("double").unwrap().doc, "n -> n*2");
+    }
+
+    #[test]
+    fn tags() {
+        let ctx = ctx();
+        ctx.register_tag(
+            "Pos",
+            EuBindDef {
+                bind: |env, bs, t| {
+                    if t > EuType::ibig(0) {
+                        env.bind_type(&bs[0], t)
+                    } else {
+                        Err(anyhow!("expected positive, got `{t:?}`").into())
+                    }
+                },
+                free: |_, _| None,
+            },
+        )
+        .unwrap();
+        let env = EuEnv::apply_str("5 \\[$Pos(x)] $x", &[], EuScope::default(), &ctx).unwrap();
+        assert_eq!(env.stack, [EuType::ibig(5)]);
+        assert!(EuEnv::apply_str("0 \\[$Pos(x)]", &[], EuScope::default(), &ctx).is_err());
+
+        let some = ctx.tag("Some").unwrap();
+        assert!(ctx.register_tag("Some", some).is_err());
+        ctx.register_tag_override("Some", ctx.tag("Pos").unwrap());
+        assert!(EuEnv::apply_str("0 \\[$Some(x)]", &[], EuScope::default(), &ctx).is_err());
+        let env = EuEnv::apply_str("1 \\[$Some(x)] $x", &[], EuScope::default(), &ctx).unwrap();
+        assert_eq!(env.stack, [EuType::ibig(1)]);
+    }
+
+    #[test]
+    fn regexes() {
+        let ctx = ctx();
+        for i in 0..=REGEX_CACHE {
+            ctx.regex("a").unwrap();
+            ctx.regex(&i.to_string()).unwrap();
+        }
+        let regexes = ctx.regexes.borrow();
+        assert_eq!(regexes.len(), REGEX_CACHE);
+        assert!(regexes.contains_key("a"));
+        assert!(!regexes.contains_key("0"));
+        drop(regexes);
+        assert!(ctx.regex("(").is_err());
+    }
+
+    #[test]
+    fn seed() {
+        let seeded = || ctx_with(|opts| opts.seed = Some(42));
+        let (ctx0, ctx1) = (seeded(), seeded());
+        assert_eq!(run("randI64 randF64", &ctx0), run("randI64 randF64", &ctx1));
+        let ctx = ctx();
+        let s = run("7 seed randI64 randI32 7 seed randI64 randI32", &ctx).unwrap();
+        assert_eq!(s[..2], s[2..]);
+        assert!(run("-1 seed", &ctx).is_err());
+    }
+
+    #[test]
+    fn fuel() {
+        let fueled = |n| ctx_with(|opts| opts.fuel = Some(n));
+        assert!(run("1 2 + 3 *", &fueled(5)).is_ok());
+        assert!(
+            run("1 2 + 3 *", &fueled(4))
+                .unwrap_err()
+                .contains("fuel exhausted")
+        );
+        assert!(
+            run("[1 2] cyc >Vec", &fueled(1000))
+                .unwrap_err()
+                .contains("fuel exhausted")
+        );
+        assert!(
+            run("SeqN0 >Vec", &fueled(1000))
+                .unwrap_err()
+                .contains("fuel exhausted")
+        );
+        let ctx = fueled(1000);
+        assert!(run("0 rpt >Vec", &ctx).is_err());
+        assert!(run("1", &ctx).unwrap_err().contains("fuel exhausted"));
+
+        let ctx = ctx_with(|opts| {
+            opts.fuel = Some(10);
+            opts.no_prelude = false;
+        });
+        let scope = EuEnv::prelude(&ctx).unwrap();
+        assert_eq!(ctx.fuel.get(), Some(10));
+        assert!(EuEnv::apply_str("1", &[], scope, &ctx).is_ok());
+        assert_eq!(ctx.fuel.get(), Some(9));
+    }
+
+    #[test]
+    fn limits() {
+        let mut ctx = ctx_with(|opts| opts.timeout = Some(0));
+        assert!(run("1", &ctx).unwrap_err().contains("timed out after 0ms"));
+        ctx.opts.timeout = Some(10);
+        ctx.reset_limits();
+        assert!(
+            run("[1] cyc (2 =) find", &ctx)
+                .unwrap_err()
+                .contains("timed out after 10ms")
+        );
+        ctx.opts.timeout = Some(600_000);
+        ctx.reset_limits();
+        assert!(run("1", &ctx).is_ok());
+
+        let ctx = ctx_with(|opts| opts.elem_budget = Some(100));
+        for code in [
+            "SeqN0 100 tk >Vec",
+            "0 30 rptN dup ++",
+            "\"ab\" 50 rptS",
+            "\"a\" 100 \" \" padS",
+            "\"a\" 100 \" \" padE",
+            "1 \"{:100}\" fmt",
+        ] {
+            ctx.reset_limits();
+            assert!(run(code, &ctx).is_ok(), "{code}");
+        }
+        for code in [
+            "SeqN0 >Vec",
+            "SeqN0 >Set",
+            "0 101 rptN",
+            "SeqN0 101 chunk 1 tk >Vec",
+            "0 60 rptN dup ++",
+            "0 10 rptN 10 rptN",
+            "[0 60 rptN 0 60 rptN] flat",
+            "\"ab\" 51 rptS",
+            "\"\" 101 \" \" padS",
+            "\"\" 101 \" \" padE",
+            "1 2 \"{:50}{:51}\" fmt",
+            "\"a\" 9999999999 \" \" padS",
+            "1 \"{:9999999999}\" fmt",
+            "SeqN0 60 tk >Vec SeqN0 60 tk >Vec",
+        ] {
+            ctx.reset_limits();
+            assert!(
+                run(code, &ctx)
+                    .unwrap_err()
+                    .contains("element budget exhausted"),
+                "{code}"
+            );
+        }
+        ctx.reset_limits();
+        assert!(run("0 60 rptN", &ctx).is_ok());
+        assert!(run("0 1000 rptN", &self::ctx()).is_ok());
+        assert_eq!(ctx.elems.get(), Some(40));
+    }
+
+    #[test]
+    fn caps() {
+        let sandboxed = |caps| {
+            ctx_with(|opts| opts.caps = caps)
+                .with_stdin(io::Cursor::new("in"))
+                .with_stdout(Buf::default())
+                .with_stderr(Buf::default())
+        };
+        let denied = |code, ctx| run(code, ctx).unwrap_err().contains("permission denied");
+
+        let ctx = sandboxed(EuCaps::Pure);
+        assert!(run("1 2 +", &ctx).is_ok());
+        assert!(denied("1 print", &ctx));
+        assert!(denied("read", &ctx));
+        let ctx = sandboxed(EuCaps::Stdout);
+        assert!(run("1 print 2 eprintL", &ctx).is_ok());
+        assert!(denied("readL", &ctx));
+        assert!(denied("\"x\" readF", &ctx));
+        assert!(denied("Env", &ctx));
+        assert!(denied("\"x\" use", &ctx));
+        let ctx = sandboxed(EuCaps::Stdio);
+        assert_eq!(run("readL 1 print", &ctx), Ok(vec!["Ok:\"in\"".into()]));
+        assert!(denied("\"x\" readF", &ctx));
+        assert!(denied("Env", &ctx));
+        let ctx = sandboxed(EuCaps::Full);
+        assert!(run("read 1 print", &ctx).is_ok());
+    }
+
+    #[test]
+    fn hook() {
+        struct Rec(Rc<RefCell<Vec<(String, usize, usize)>>>);
+
+        impl EuHook for Rec {
+            fn before(&mut self, env: &EuEnv<'_>, t: &EuSyn<'_>, depth: usize) -> EuRes<()> {
+                self.0
+                    .borrow_mut()
+                    .push((format!("{t:?}"), env.stack.len(), depth));
+                Ok(())
+            }
+        }
+
+        let steps = Rc::new(RefCell::new(vec![]));
+        let ctx = ctx().with_hook(Rec(steps.clone()));
+        EuEnv::apply_str("(1 +) \\[f] 2 f 3", &[], EuScope::default(), &ctx).unwrap();
+        let steps = steps.borrow();
+        let at = |w: &str| steps.iter().find(|(t, ..)| t == w).unwrap().2;
+        assert_eq!(steps.len(), 7);
+        assert!(at("+") > at("f"));
+        assert_eq!(at("f"), at("3"));
+        assert_eq!(steps.last().unwrap().1, 1);
+    }
+
+    #[test]
+    fn hook_eval() {
+        struct Eval(Rc<Cell<usize>>);
+
+        impl EuHook for Eval {
+            fn before(&mut self, env: &EuEnv<'_>, _: &EuSyn<'_>, _: usize) -> EuRes<()> {
+                let res = EuEnv::apply_str("1 1 +", &[], env.scope.clone(), env.ctx)?;
+                self.0.set(self.0.get() + res.stack.len());
+                Ok(())
+            }
+        }
+
+        let evals = Rc::new(Cell::new(0));
+        let ctx = ctx().with_hook(Eval(evals.clone()));
+        EuEnv::apply_str("1 2", &[], EuScope::default(), &ctx).unwrap();
+        assert_eq!(evals.get(), 2);
+        assert!(ctx.hook.borrow().is_some());
+    }
+
+    #[test]
+    fn profile() {
+        let ctx = ctx().with_profile();
+        EuEnv::apply_str("(1 +) \\[f] 2 f f dup", &[], EuScope::default(), &ctx).unwrap();
+        let profile = ctx.profile.borrow();
+        let p = profile.as_ref().unwrap();
+        assert_eq!(p.word("f").unwrap().calls, 2);
+        assert_eq!(p.word("+").unwrap().calls, 2);
+        assert_eq!(p.word("dup").unwrap().calls, 1);
+        assert!(p.stacks().any(|(path, _)| path == ["f", "+"]));
+        assert!(p.folded().contains("f;+ "));
     }
 }
@@ -8,7 +8,10 @@ use ecow::EcoVec;
 use ordermap::OrderMap;
 
 use crate::{
-    env::EuEnv,
+    env::{
+        EuEnv,
+        EuEnvCtx,
+    },
     types::{
         EuBind,
         EuRes,
@@ -16,9 +19,11 @@ use crate::{
     },
 };
 
+#[derive(Clone, Copy)]
 pub struct EuBindDef {
     pub bind: for<'eu> fn(&mut EuEnv<'eu>, &EcoVec<EuBind<'eu>>, EuType<'eu>) -> EuRes<()>,
-    pub free: for<'eu> fn(EcoVec<EuBind<'eu>>) -> Option<EuType<'eu>>,
+    /// Builds the value the tag matches exactly, if any.
+    pub free: for<'eu> fn(EcoVec<EuBind<'eu>>, &EuEnvCtx) -> Option<EuType<'eu>>,
 }
 
 pub const BIND: phf::Map<&str, EuBindDef> = phf::phf_map! {
@@ -39,9 +44,9 @@ pub const VECZ: EuBindDef = EuBindDef {
         }
         Ok(())
     },
-    free: |bs| {
+    free: |bs, ctx| {
         bs.into_iter()
-            .map(EuBind::to_free)
+            .map(|b| b.to_free(ctx))
             .try_collect()
             .map(EuType::Vec)
     },
@@ -55,12 +60,12 @@ pub const MAP: EuBindDef = EuBindDef {
             kvs: &Rc<OrderMap<EuType<'eu>, EuType<'eu>>>,
         ) -> EuRes<()> {
             match b {
-                EuBind::Word(w) => {
+                EuBind::Word(w, _) => {
                     let k = EuType::Str(w.clone());
                     env.scope.insert(w.clone(), get_key(kvs, &k)?.clone());
                 }
                 EuBind::Bind(b0, b1) => {
-                    if let Some(k) = b0.clone().to_free() {
+                    if let Some(k) = b0.clone().to_free(env.ctx) {
                         env.bind_type(b1, get_key(kvs, &k)?.clone())?;
                     } else {
                         worst_bind(env, b1, kvs)?;
@@ -73,7 +78,7 @@ pub const MAP: EuBindDef = EuBindDef {
                         }
                     }
                 }
-                _ if let Some(k) = b.clone().to_free() => {
+                _ if let Some(k) = b.clone().to_free(env.ctx) => {
                     get_key(kvs, &k)?;
                 }
                 _ => worst_bind(env, b, kvs)?,
@@ -120,10 +125,10 @@ pub const SOME: EuBindDef = EuBindDef {
             Err(anyhow!("expected Some, got `{t:?}`").into())
         }
     },
-    free: |bs| {
+    free: |bs, ctx| {
         bs.into_iter()
             .next()
-            .and_then(EuBind::to_free)
+            .and_then(|b| b.to_free(ctx))
             .map(Some)
             .map(EuType::opt)
     },
@@ -133,7 +138,7 @@ pub const NONE: EuBindDef = EuBindDef {
     bind: |_, _, t| {
         matches!(t, EuType::Opt(None)).ok_or_else(|| anyhow!("expected None, got `{t:?}`").into())
     },
-    free: |_| Some(EuType::opt(None)),
+    free: |_, _| Some(EuType::opt(None)),
 };
 
 pub const OK: EuBindDef = EuBindDef {
@@ -145,10 +150,10 @@ pub const OK: EuBindDef = EuBindDef {
             Err(anyhow!("expected Ok, got `{t:?}`").into())
         }
     },
-    free: |bs| {
+    free: |bs, ctx| {
         bs.into_iter()
             .next()
-            .and_then(EuBind::to_free)
+            .and_then(|b| b.to_free(ctx))
             .map(Ok)
             .map(EuType::res)
     },
@@ -163,10 +168,10 @@ pub const ERR: EuBindDef = EuBindDef {
             Err(anyhow!("expected Err, got `{t:?}`").into())
         }
     },
-    free: |bs| {
+    free: |bs, ctx| {
         bs.into_iter()
             .next()
-            .and_then(EuBind::to_free)
+            .and_then(|b| b.to_free(ctx))
             .map(Err)
             .map(EuType::res)
     },
@@ -0,0 +1,46 @@
+use crate::{
+    fns::EuDef,
+    types::{
+        EuDsvOpts,
+        EuType,
+    },
+};
+
+macro_rules! from_dsv {
+    ($name:ident, $opts:expr) => {
+        pub const $name: EuDef = |env| {
+            let a0 = env.arg("a0 (str)")?;
+            env.push(EuType::res_str(EuType::from_dsv(&a0.to_str(), $opts)));
+            Ok(())
+        };
+    };
+}
+
+macro_rules! to_dsv {
+    ($name:ident, $opts:expr) => {
+        pub const $name: EuDef = |env| {
+            let a0 = env.arg("a0")?;
+            env.push(EuType::str(a0.to_dsv($opts)?));
+            Ok(())
+        };
+    };
+}
+
+from_dsv!(FROM_CSV, EuDsvOpts::CSV);
+from_dsv!(FROM_TSV, EuDsvOpts::TSV);
+to_dsv!(TO_CSV, EuDsvOpts::CSV);
+to_dsv!(TO_TSV, EuDsvOpts::TSV);
+
+pub const FROM_DSV: EuDef = |env| {
+    let a1 = EuDsvOpts::from_map(env.arg("a1 (opts)")?)?;
+    let a0 = env.arg("a0 (str)")?;
+    env.push(EuType::res_str(EuType::from_dsv(&a0.to_str(), a1)));
+    Ok(())
+};
+
+pub const TO_DSV: EuDef = |env| {
+    let a1 = EuDsvOpts::from_map(env.arg("a1 (opts)")?)?;
+    let a0 = env.arg("a0")?;
+    env.push(EuType::str(a0.to_dsv(a1)?));
+    Ok(())
+};
@@ -16,7 +16,10 @@ pub const WRAP_EXPR: EuDef = |env| {
     Ok(())
 };
 
-pub const EVAL: EuDef = |env| env.arg("a0 (eval)")?.for_rec(&mut |f| env.eval_iter(f));
+pub const EVAL: EuDef = |env| {
+    env.arg("a0 (eval)")?
+        .for_rec(&mut |f| env.eval_call("#", f))
+};
 
 pub const TAP: EuDef = |env| {
     env.arg("a0 (eval)")?.for_rec(&mut |f| {
@@ -29,7 +32,7 @@ pub const AND_EVAL: EuDef = |env| {
     let a1 = env.arg("a1 (eval)")?;
     let a0 = env.arg("a0 (cond)")?.into();
     if a0 {
-        a1.for_rec(&mut |f| env.eval_iter(f))
+        a1.for_rec(&mut |f| env.eval_call("&#", f))
     } else {
         Ok(())
     }
@@ -41,7 +44,7 @@ pub const OR_EVAL: EuDef = |env| {
     if a0 {
         Ok(())
     } else {
-        a1.for_rec(&mut |f| env.eval_iter(f))
+        a1.for_rec(&mut |f| env.eval_call("|#", f))
     }
 };
 
@@ -49,5 +52,5 @@ pub const IF_EVAL: EuDef = |env| {
     let a2 = env.arg("a2 (evalT)")?;
     let a1 = env.arg("a1 (evalF)")?;
     let a0 = env.arg("a0 (cond)")?.into();
-    if a0 { a1 } else { a2 }.for_rec(&mut |f| env.eval_iter(f))
+    if a0 { a1 } else { a2 }.for_rec(&mut |f| env.eval_call("&|#", f))
 };
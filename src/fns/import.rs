@@ -0,0 +1,25 @@
+use ordermap::OrderMap;
+
+use crate::{
+    fns::EuDef,
+    types::EuType,
+};
+
+pub const IMPORT: EuDef = |env| {
+    let a0 = env.arg("a0 (path)")?.to_str();
+    let mut kvs: Vec<_> = env
+        .import(&a0)?
+        .into_iter()
+        .map(|(k, v)| (EuType::Str(k), v))
+        .collect();
+    kvs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    env.push(EuType::map_(kvs.into_iter().collect::<OrderMap<_, _>>()));
+    Ok(())
+};
+
+pub const USE: EuDef = |env| {
+    let a0 = env.arg("a0 (path)")?.to_str();
+    let scope = env.import(&a0)?;
+    env.scope.extend(scope);
+    Ok(())
+};
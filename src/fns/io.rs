@@ -1,25 +1,37 @@
-use std::io;
+use std::{
+    fs,
+    io::Write,
+};
 
 use anyhow::anyhow;
 
 use crate::{
+    EuCaps,
     fns::EuDef,
     types::EuType,
 };
 
 pub const READ: EuDef = |env| {
+    env.ctx.require(EuCaps::Stdio)?;
+    let mut res = String::new();
     env.push(EuType::res_str(
-        io::read_to_string(io::stdin())
-            .map(EuType::str)
+        env.ctx
+            .stdin
+            .borrow_mut()
+            .read_to_string(&mut res)
+            .map(|_| EuType::str(res))
             .map_err(|e| anyhow!(e).into()),
     ));
     Ok(())
 };
 
 pub const READLN: EuDef = |env| {
+    env.ctx.require(EuCaps::Stdio)?;
     let mut res = String::new();
     env.push(EuType::res_str(
-        io::stdin()
+        env.ctx
+            .stdin
+            .borrow_mut()
             .read_line(&mut res)
             .map(|_| EuType::str(res))
             .map_err(|e| anyhow!(e).into()),
@@ -28,11 +40,138 @@ pub const READLN: EuDef = |env| {
 };
 
 pub const PRINT: EuDef = |env| {
-    print!("{}", env.arg("a0")?);
+    env.ctx.require(EuCaps::Stdout)?;
+    let a0 = env.arg("a0")?;
+    write!(env.ctx.stdout.borrow_mut(), "{a0}").map_err(|e| anyhow!(e))?;
     Ok(())
 };
 
 pub const PRINTLN: EuDef = |env| {
-    println!("{}", env.arg("a0")?);
+    env.ctx.require(EuCaps::Stdout)?;
+    let a0 = env.arg("a0")?;
+    writeln!(env.ctx.stdout.borrow_mut(), "{a0}").map_err(|e| anyhow!(e))?;
+    Ok(())
+};
+
+pub const EPRINT: EuDef = |env| {
+    env.ctx.require(EuCaps::Stdout)?;
+    let a0 = env.arg("a0")?;
+    write!(env.ctx.stderr.borrow_mut(), "{a0}").map_err(|e| anyhow!(e))?;
+    Ok(())
+};
+
+pub const EPRINTLN: EuDef = |env| {
+    env.ctx.require(EuCaps::Stdout)?;
+    let a0 = env.arg("a0")?;
+    writeln!(env.ctx.stderr.borrow_mut(), "{a0}").map_err(|e| anyhow!(e))?;
+    Ok(())
+};
+
+pub const READ_FILE: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a0 = env.arg("a0 (path)")?.to_str();
+    env.push(EuType::res_str(
+        fs::read_to_string(&*a0)
+            .map(EuType::str)
+            .map_err(|e| anyhow!(e).into()),
+    ));
+    Ok(())
+};
+
+pub const WRITE_FILE: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a1 = env.arg("a1 (path)")?.to_str();
+    let a0 = env.arg("a0")?.to_str();
+    env.push(EuType::res_str(
+        fs::write(&*a1, &*a0)
+            .map(|()| EuType::Str(a1))
+            .map_err(|e| anyhow!(e).into()),
+    ));
+    Ok(())
+};
+
+pub const APPEND_FILE: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a1 = env.arg("a1 (path)")?.to_str();
+    let a0 = env.arg("a0")?.to_str();
+    env.push(EuType::res_str(
+        fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&*a1)
+            .and_then(|mut f| f.write_all(a0.as_bytes()))
+            .map(|()| EuType::Str(a1))
+            .map_err(|e| anyhow!(e).into()),
+    ));
+    Ok(())
+};
+
+pub const LIST_DIR: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a0 = env.arg("a0 (path)")?.to_str();
+    env.push(EuType::res_str(
+        fs::read_dir(&*a0)
+            .and_then(|it| {
+                it.map(|r| r.map(|e| EuType::str(e.path().to_string_lossy().into_owned())))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map(|mut ts| {
+                ts.sort();
+                EuType::vec(ts)
+            })
+            .map_err(|e| anyhow!(e).into()),
+    ));
+    Ok(())
+};
+
+pub const EXISTS: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a0 = env.arg("a0 (path)")?.to_str();
+    env.push(EuType::res_str(
+        fs::exists(&*a0)
+            .map(EuType::Bool)
+            .map_err(|e| anyhow!(e).into()),
+    ));
+    Ok(())
+};
+
+pub const MAKE_DIR: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a0 = env.arg("a0 (path)")?.to_str();
+    env.push(EuType::res_str(
+        fs::create_dir_all(&*a0)
+            .map(|()| EuType::Str(a0))
+            .map_err(|e| anyhow!(e).into()),
+    ));
+    Ok(())
+};
+
+pub const REMOVE: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a0 = env.arg("a0 (path)")?.to_str();
+    env.push(EuType::res_str(
+        fs::symlink_metadata(&*a0)
+            .and_then(|m| {
+                if m.is_dir() {
+                    fs::remove_dir(&*a0)
+                } else {
+                    fs::remove_file(&*a0)
+                }
+            })
+            .map(|()| EuType::Str(a0))
+            .map_err(|e| anyhow!(e).into()),
+    ));
+    Ok(())
+};
+
+pub const RENAME: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let a1 = env.arg("a1 (to)")?.to_str();
+    let a0 = env.arg("a0 (from)")?.to_str();
+    env.push(EuType::res_str(
+        fs::rename(&*a0, &*a1)
+            .map(|()| EuType::Str(a1))
+            .map_err(|e| anyhow!(e).into()),
+    ));
     Ok(())
 };
@@ -0,0 +1,16 @@
+use crate::{
+    fns::EuDef,
+    types::EuType,
+};
+
+pub const TO_JSON: EuDef = |env| {
+    let a0 = env.arg("a0")?;
+    env.push(EuType::str(a0.to_json()?));
+    Ok(())
+};
+
+pub const FROM_JSON: EuDef = |env| {
+    let a0 = env.arg("a0 (str)")?;
+    env.push(EuType::res_str(EuType::from_json(&a0.to_str())));
+    Ok(())
+};
@@ -1,31 +1,41 @@
 pub mod bind;
 mod bool;
 mod cmp;
+mod dsv;
 mod expr;
+mod import;
 mod io;
+mod json;
 mod macros;
 mod map;
 mod num;
+mod re;
 mod rng;
 mod seq;
 mod set;
 mod stack;
 mod str;
+mod sys;
 mod r#try;
 mod vec;
 mod vecz;
 
 pub use bool::*;
 pub use cmp::*;
+pub use dsv::*;
 pub use expr::*;
+pub use import::*;
 pub use io::*;
+pub use json::*;
 pub use map::*;
 pub use num::*;
+pub use re::*;
 pub use rng::*;
 pub use seq::*;
 pub use set::*;
 pub use stack::*;
 pub use str::*;
+pub use sys::*;
 pub use r#try::*;
 pub use vec::*;
 pub use vecz::*;
@@ -73,6 +83,25 @@ pub const CORE: phf::Map<&str, EuDef> = phf::phf_map! {
     "readL" => READLN,
     "print" => PRINT,
     "printL" => PRINTLN,
+    "eprint" => EPRINT,
+    "eprintL" => EPRINTLN,
+    "readF" => READ_FILE,
+    "writeF" => WRITE_FILE,
+    "appendF" => APPEND_FILE,
+    "ls" => LIST_DIR,
+    "exists?" => EXISTS,
+    "mkdir" => MAKE_DIR,
+    "rm" => REMOVE,
+    "mv" => RENAME,
+
+    // sys
+    "Args" => ARGS,
+    "Env" => ENV,
+    "exit" => EXIT,
+
+    // import
+    "import" => IMPORT,
+    "use" => USE,
 
     // cmp
     "<=>" => CMP,
@@ -156,9 +185,52 @@ pub const CORE: phf::Map<&str, EuDef> = phf::phf_map! {
     "randI32" => RAND_I32,
     "randI64" => RAND_I64,
     "randF64" => RAND_F64,
+    "seed" => SEED,
 
     // str
     ">Str" => TO_STR,
+    "show" => SHOW,
+    "readS" => READ_SHOW,
+
+    "split" => SPLIT,
+    "splitW" => SPLIT_WS,
+    "lines" => LINES,
+    "join" => JOIN,
+    "trim" => TRIM,
+    "trimS" => TRIM_START,
+    "trimE" => TRIM_END,
+    "upper" => UPPER,
+    "lower" => LOWER,
+    "starts?" => STARTS_WITH,
+    "ends?" => ENDS_WITH,
+    "hasS" => CONTAINS,
+    "idx" => FIND_STR,
+    "repl" => REPLACE,
+    "repl1" => REPLACE_FIRST,
+    "padS" => PAD_START,
+    "padE" => PAD_END,
+    "rptS" => REPEAT_STR,
+    "fmt" => FMT,
+
+    // json
+    ">Json" => TO_JSON,
+    "Json>" => FROM_JSON,
+
+    // dsv
+    ">Csv" => TO_CSV,
+    "Csv>" => FROM_CSV,
+    ">Tsv" => TO_TSV,
+    "Tsv>" => FROM_TSV,
+    ">Dsv" => TO_DSV,
+    "Dsv>" => FROM_DSV,
+
+    // re
+    "match?" => IS_MATCH,
+    "reFind" => RE_FIND,
+    "reAll" => RE_FIND_ALL,
+    "reCap" => RE_CAPTURES,
+    "reRepl" => RE_REPLACE,
+    "reSplit" => RE_SPLIT,
 
     // try
     "None" => NONE,
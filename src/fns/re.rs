@@ -0,0 +1,42 @@
+use crate::{
+    fns::EuDef,
+    types::EuType,
+};
+
+macro_rules! re_2_to_1 {
+    ($name:ident, $f:ident) => {
+        pub const $name: EuDef = |env| {
+            let a1 = env.arg("a1 (regex)")?;
+            let a0 = env.arg("a0")?;
+            let ctx = env.ctx;
+            env.push(a0.vecz2(a1, move |t, p| Ok(t.$f(&ctx.regex(&p.to_str())?)))?);
+            Ok(())
+        };
+    };
+}
+
+pub const IS_MATCH: EuDef = |env| {
+    let a1 = env.arg("a1 (regex)")?;
+    let a0 = env.arg("a0")?;
+    let ctx = env.ctx;
+    env.push(a0.vecz2(a1, move |t, p| {
+        Ok(EuType::Bool(ctx.regex(&p.to_str())?.is_match(&t.to_str())))
+    })?);
+    Ok(())
+};
+
+re_2_to_1!(RE_FIND, re_find);
+re_2_to_1!(RE_FIND_ALL, re_find_all);
+re_2_to_1!(RE_CAPTURES, re_captures);
+re_2_to_1!(RE_SPLIT, re_split);
+
+pub const RE_REPLACE: EuDef = |env| {
+    let a2 = env.arg("a2 (to)")?.to_str();
+    let a1 = env.arg("a1 (regex)")?;
+    let a0 = env.arg("a0")?;
+    let ctx = env.ctx;
+    env.push(a0.vecz2(a1, move |t, p| {
+        Ok(t.re_replace(&ctx.regex(&p.to_str())?, &a2))
+    })?);
+    Ok(())
+};
@@ -1,4 +1,8 @@
-use rand::RngExt;
+use rand::{
+    RngExt,
+    SeedableRng,
+    rngs::StdRng,
+};
 
 use crate::{
     fns::EuDef,
@@ -20,3 +24,9 @@ fn rand(name: String) {
 rand!(I32);
 rand!(I64);
 rand!(F64);
+
+pub const SEED: EuDef = |env| {
+    let a0 = env.arg("a0 (seed)")?;
+    *env.ctx.rng.borrow_mut() = Box::new(StdRng::seed_from_u64(a0.try_u64()?));
+    Ok(())
+};
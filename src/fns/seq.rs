@@ -6,7 +6,7 @@ use crate::{
 };
 
 pub const SEQ_N0: EuDef = |env| {
-    env.push(EuType::seq((0..).map(EuType::ibig).map(Ok)));
+    env.push(EuType::seq((0..).map(EuType::ibig).map(Ok)).metered(env.ctx));
     Ok(())
 };
 
@@ -31,19 +31,24 @@ pub const UNFOLD: EuDef = |env| {
 
 pub const REPEAT: EuDef = |env| {
     let a0 = env.arg("a0")?;
-    env.push(EuType::seq(a0.repeat()));
+    env.push(EuType::seq(a0.repeat()).metered(env.ctx));
     Ok(())
 };
 
 pub const REPEAT_N: EuDef = |env| {
     let a1 = env.arg("a1 (num)")?;
     let a0 = env.arg("a0")?;
-    env.push(a1.vecz1(|n| a0.repeat_n(n.try_usize()?).map(EuType::Vec))?);
+    let ctx = env.ctx;
+    env.push(a1.vecz1(|n| {
+        let n = n.try_usize()?;
+        ctx.charge_elems(n.saturating_mul(1 + a0.elems()))?;
+        a0.repeat_n(n).map(EuType::Vec)
+    })?);
     Ok(())
 };
 
 pub const CYCLE: EuDef = |env| {
     let a0 = env.arg("a0")?;
-    env.push(EuType::seq(a0.cycle()));
+    env.push(EuType::seq(a0.cycle()).metered(env.ctx));
     Ok(())
 };
@@ -1,8 +1,37 @@
+use anyhow::anyhow;
+use ecow::EcoVec;
+use winnow::Parser;
+
 use crate::{
     fns::EuDef,
-    types::EuType,
+    parser::fmt_template,
+    types::{
+        EuFmtPiece,
+        EuType,
+    },
 };
 
+macro_rules! str_1_to_1 {
+    ($name:ident, $f:ident) => {
+        pub const $name: EuDef = |env| {
+            let a0 = env.arg("a0")?;
+            env.push(a0.vecz1(|t| Ok(EuType::Str(t.to_str().$f())))?);
+            Ok(())
+        };
+    };
+}
+
+macro_rules! str_2_to_bool {
+    ($name:ident, $f:ident) => {
+        pub const $name: EuDef = |env| {
+            let a1 = env.arg("a1 (str)")?;
+            let a0 = env.arg("a0")?;
+            env.push(a0.vecz2(a1, |t, p| Ok(EuType::Bool(t.to_str().$f(&*p.to_str()))))?);
+            Ok(())
+        };
+    };
+}
+
 pub const TO_STR: EuDef = |env| {
     let a0 = match env.arg("a0")? {
         t @ EuType::Str(_) => t,
@@ -11,3 +40,132 @@ pub const TO_STR: EuDef = |env| {
     env.push(a0);
     Ok(())
 };
+
+pub const SHOW: EuDef = |env| {
+    let a0 = env.arg("a0")?;
+    env.push(EuType::str(a0.to_source()));
+    Ok(())
+};
+
+pub const READ_SHOW: EuDef = |env| {
+    let a0 = env.arg("a0 (str)")?;
+    env.push(EuType::res_str(EuType::from_source(&a0.to_str())));
+    Ok(())
+};
+
+pub const SPLIT: EuDef = |env| {
+    let a1 = env.arg("a1 (sep)")?;
+    let a0 = env.arg("a0")?;
+    env.push(a0.vecz2(a1, |t, sep| Ok(t.split_str(&sep.to_str())))?);
+    Ok(())
+};
+
+pub const SPLIT_WS: EuDef = |env| {
+    let a0 = env.arg("a0")?;
+    env.push(a0.vecz1(|t| Ok(t.split_ws()))?);
+    Ok(())
+};
+
+pub const LINES: EuDef = |env| {
+    let a0 = env.arg("a0")?;
+    env.push(a0.vecz1(|t| Ok(t.lines()))?);
+    Ok(())
+};
+
+pub const JOIN: EuDef = |env| {
+    let a1 = env.arg("a1 (sep)")?;
+    let a0 = env.arg("a0")?;
+    env.push(a1.vecz1(|sep| a0.join_str(&sep.to_str()))?);
+    Ok(())
+};
+
+str_1_to_1!(TRIM, trim);
+str_1_to_1!(TRIM_START, trim_start);
+str_1_to_1!(TRIM_END, trim_end);
+str_1_to_1!(UPPER, to_uppercase);
+str_1_to_1!(LOWER, to_lowercase);
+
+str_2_to_bool!(STARTS_WITH, starts_with);
+str_2_to_bool!(ENDS_WITH, ends_with);
+str_2_to_bool!(CONTAINS, contains);
+
+pub const FIND_STR: EuDef = |env| {
+    let a1 = env.arg("a1 (str)")?;
+    let a0 = env.arg("a0")?;
+    env.push(a0.vecz2(a1, |t, p| Ok(t.find_str(&p.to_str())))?);
+    Ok(())
+};
+
+pub const REPLACE: EuDef = |env| {
+    let a2 = env.arg("a2 (to)")?.to_str();
+    let a1 = env.arg("a1 (from)")?.to_str();
+    let a0 = env.arg("a0")?;
+    env.push(a0.vecz1(move |t| Ok(EuType::str(t.to_str().replace(&*a1, &a2))))?);
+    Ok(())
+};
+
+pub const REPLACE_FIRST: EuDef = |env| {
+    let a2 = env.arg("a2 (to)")?.to_str();
+    let a1 = env.arg("a1 (from)")?.to_str();
+    let a0 = env.arg("a0")?;
+    env.push(a0.vecz1(move |t| Ok(EuType::str(t.to_str().replacen(&*a1, &a2, 1))))?);
+    Ok(())
+};
+
+pub const PAD_START: EuDef = |env| {
+    let a2 = env.arg("a2 (fill)")?.to_str();
+    let a1 = env.arg("a1 (int)")?;
+    let a0 = env.arg("a0")?;
+    let ctx = env.ctx;
+    env.push(a0.vecz2(a1, move |t, n| {
+        let (s, n) = (EuType::Str(t.to_str()), n.try_usize()?);
+        ctx.charge_elems(n.saturating_sub(s.elems()))?;
+        s.pad_str(n, &a2, true)
+    })?);
+    Ok(())
+};
+
+pub const PAD_END: EuDef = |env| {
+    let a2 = env.arg("a2 (fill)")?.to_str();
+    let a1 = env.arg("a1 (int)")?;
+    let a0 = env.arg("a0")?;
+    let ctx = env.ctx;
+    env.push(a0.vecz2(a1, move |t, n| {
+        let (s, n) = (EuType::Str(t.to_str()), n.try_usize()?);
+        ctx.charge_elems(n.saturating_sub(s.elems()))?;
+        s.pad_str(n, &a2, false)
+    })?);
+    Ok(())
+};
+
+pub const REPEAT_STR: EuDef = |env| {
+    let a1 = env.arg("a1 (int)")?;
+    let a0 = env.arg("a0")?;
+    let ctx = env.ctx;
+    env.push(a0.vecz2(a1, move |t, n| {
+        let (s, n) = (t.to_str(), n.try_usize()?);
+        ctx.charge_elems(s.chars().count().saturating_mul(n))?;
+        EuType::Str(s).repeat_str(n)
+    })?);
+    Ok(())
+};
+
+pub const FMT: EuDef = |env| {
+    let a0 = env.arg("a0 (template)")?.to_str();
+    let ps = fmt_template
+        .parse(&a0)
+        .map_err(|e| anyhow!(e.to_string()))?;
+    let (n, named) = EuFmtPiece::arity(&ps);
+    env.ctx.charge_elems(EuFmtPiece::width(&ps))?;
+    let kvs = if named {
+        Some(env.arg("a1 (map)")?.to_map()?)
+    } else {
+        None
+    };
+    env.check_nargs(n)?;
+    let i = env.stack.len() - n;
+    let args = EcoVec::from(&env.stack[i..]);
+    env.stack.truncate(i);
+    env.push(EuType::format(&ps, &args, kvs.as_deref())?);
+    Ok(())
+};
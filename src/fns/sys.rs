@@ -0,0 +1,43 @@
+use std::env;
+
+use anyhow::anyhow;
+use ordermap::OrderMap;
+
+use crate::{
+    EuCaps,
+    fns::EuDef,
+    types::EuType,
+};
+
+pub const ARGS: EuDef = |env| {
+    env.push(EuType::vec(
+        env.ctx
+            .opts
+            .args
+            .iter()
+            .map(|s| EuType::str(s.as_str()))
+            .collect::<Vec<_>>(),
+    ));
+    Ok(())
+};
+
+pub const ENV: EuDef = |env| {
+    env.ctx.require(EuCaps::Full)?;
+    let mut kvs: Vec<_> = env::vars_os()
+        .map(|(k, v)| {
+            (
+                EuType::str(k.to_string_lossy().into_owned()),
+                EuType::str(v.to_string_lossy().into_owned()),
+            )
+        })
+        .collect();
+    kvs.sort();
+    env.push(EuType::map_(kvs.into_iter().collect::<OrderMap<_, _>>()));
+    Ok(())
+};
+
+pub const EXIT: EuDef = |env| {
+    let code = env.arg("a0 (code)")?.try_i32()?;
+    env.ctx.exit.set(Some(code));
+    Err(anyhow!("exit {code}").into())
+};
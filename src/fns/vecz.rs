@@ -59,7 +59,22 @@ pub const INSERT: EuDef = |env| {
     Ok(())
 };
 
-f_2_to_try_1!(APPEND);
+pub const APPEND: EuDef = |env| {
+    let a1 = env.arg("a1")?;
+    let a0 = env.arg("a0")?;
+    if !(a0.is_seq() || a1.is_seq() || a0.is_expr() || a1.is_expr()) {
+        let len = |t: &EuType| {
+            if t.is_many() || t.is_str() {
+                t.elems()
+            } else {
+                1
+            }
+        };
+        env.ctx.charge_elems(len(&a0) + len(&a1))?;
+    }
+    env.push(a0.append(a1)?);
+    Ok(())
+};
 
 pub const POP_BACK: EuDef = |env| {
     let a0 = env.arg("a0")?;
@@ -238,7 +253,7 @@ pub const MULTI_ZIP: EuDef = |env| {
 
 pub const MULTI_CPROD: EuDef = |env| {
     let a0 = env.arg("a0")?.to_vec()?;
-    env.push(EuType::seq(EuType::multi_cartesian_product(a0)));
+    env.push(EuType::seq(EuType::multi_cartesian_product(a0)).metered(env.ctx));
     Ok(())
 };
 
@@ -11,6 +11,7 @@
 pub mod env;
 pub mod fns;
 pub mod parser;
+pub mod profile;
 pub mod types;
 pub mod utils;
 
@@ -23,7 +24,19 @@ use env::EuEnv;
 
 use crate::env::EuEnvCtx;
 
-wit_bindgen::generate!();
+#[allow(
+    clippy::too_many_arguments,
+    reason = "wit_bindgen glue flattens EuEnvOpts into arguments"
+)]
+mod glue {
+    wit_bindgen::generate!();
+}
+
+use glue::Guest;
+pub use glue::{
+    EuCaps,
+    EuEnvOpts,
+};
 
 struct Glue;
 
@@ -31,14 +44,15 @@ impl Guest for Glue {
     fn run_euph(code: String, opts: EuEnvOpts) {
         let ctx = EuEnvCtx::new(opts, Arc::new(AtomicBool::new(true)), rand::rng());
 
-        match EuEnv::apply_str(&code, &[], imbl::GenericHashMap::new(), &ctx) {
+        match EuEnv::prelude(&ctx).and_then(|scope| {
+            ctx.reset_limits();
+            EuEnv::apply_str(&code, &[], scope, &ctx)
+        }) {
+            _ if ctx.exit.get().is_some() => {}
             Ok(env) => println!("{env}"),
-            Err(e) => {
-                eprintln!("ERR:");
-                e.0.chain().for_each(|c| eprintln!("{c}"));
-            }
+            Err(e) => eprint!("{}", e.report()),
         }
     }
 }
 
-export!(Glue);
+glue::export!(Glue with_types_in glue);
@@ -2,24 +2,40 @@ use std::sync::{
     Arc,
     atomic::AtomicBool,
 };
+#[cfg(not(target_arch = "wasm32"))]
+mod debugger;
+
 #[cfg(not(target_arch = "wasm32"))]
 use std::{
     fs,
     io,
+    mem,
     path,
     sync::atomic::Ordering,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
-use clap::Parser;
+use clap::{
+    Parser,
+    Subcommand,
+    ValueEnum,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use debugger::Debugger;
 use euph::{
+    EuCaps,
     EuEnvOpts,
     env::{
         EuEnv,
         EuEnvCtx,
     },
+    parser::{
+        check,
+        pretty,
+    },
+    profile::EuProfile,
+    types::EuLoc,
 };
-use imbl::GenericHashMap;
 #[cfg(not(target_arch = "wasm32"))]
 use rustyline::{
     DefaultEditor,
@@ -28,9 +44,11 @@ use rustyline::{
 
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
+#[expect(clippy::struct_excessive_bools, reason = "CLI flags")]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct Cli {
-    #[arg(group = "input")]
+    #[command(subcommand)]
+    cmd: Option<Cmd>,
     /// Evaluate code from the given file
     file: Option<path::PathBuf>,
     /// Evaluate the given string
@@ -42,9 +60,97 @@ struct Cli {
     /// Turn on debug mode
     #[arg(long, default_value_t = false)]
     debug: bool,
+    /// Do not load the standard prelude
+    #[arg(long, default_value_t = false)]
+    no_prelude: bool,
+    /// Step through the program interactively
+    #[arg(long, default_value_t = false, conflicts_with = "stdin")]
+    debugger: bool,
+    /// Print call counts and timings per word at exit
+    #[arg(long, default_value_t = false)]
+    profile: bool,
+    /// Write folded stacks for flamegraph tools to the given file
+    #[arg(long, value_name = "FILE")]
+    profile_folded: Option<path::PathBuf>,
     /// Print final program state
     #[arg(long, default_value_t = false)]
     dump: bool,
+    /// Report all syntax errors without evaluating
+    #[arg(long, default_value_t = false)]
+    check: bool,
+    /// Seed the random number generator for reproducible runs
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Abort after evaluating this many steps
+    #[arg(long)]
+    fuel: Option<u64>,
+    /// Abort after running for this many milliseconds
+    #[arg(long, value_name = "MS")]
+    timeout: Option<u64>,
+    /// Abort after building this many collection or sequence elements in total
+    #[arg(long, value_name = "N")]
+    elem_budget: Option<u64>,
+    /// Which I/O words the program may use
+    #[arg(long, value_enum, default_value_t = Caps::Full)]
+    caps: Caps,
+    /// Add a directory to the module search path
+    #[arg(short = 'I', long = "include", value_name = "DIR")]
+    path: Vec<String>,
+    /// Arguments passed to the program
+    #[arg(trailing_var_arg = true, num_args = 0..)]
+    args: Vec<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Cli {
+    /// Arguments passed to the program. Code given with `-s` or `-i` takes no
+    /// file, so the first positional is an argument as well.
+    fn args(&mut self) -> Vec<String> {
+        let mut args = mem::take(&mut self.args);
+        if (self.string.is_some() || self.stdin)
+            && let Some(f) = self.file.take()
+        {
+            args.insert(0, f.to_string_lossy().into_owned());
+        }
+        args
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Subcommand)]
+enum Cmd {
+    /// Format a source file in place
+    Fmt {
+        file: path::PathBuf,
+        /// Only check whether the file is formatted
+        #[arg(long, default_value_t = false)]
+        check: bool,
+    },
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, ValueEnum)]
+enum Caps {
+    /// No I/O at all
+    Pure,
+    /// Only printing to stdout and stderr
+    Stdout,
+    /// Printing, and reading from stdin
+    Stdio,
+    /// Everything, including stdin, files and the environment
+    Full,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<Caps> for EuCaps {
+    fn from(c: Caps) -> Self {
+        match c {
+            Caps::Pure => EuCaps::Pure,
+            Caps::Stdout => EuCaps::Stdout,
+            Caps::Stdio => EuCaps::Stdio,
+            Caps::Full => EuCaps::Full,
+        }
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -52,11 +158,21 @@ fn main() {}
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    let args = cli.args();
+
+    if let Some(Cmd::Fmt { file, check }) = cli.cmd {
+        fmt(&file, check);
+        return;
+    }
 
+    let mut path = cli.path;
     let res: anyhow::Result<String> = if let Some(s) = cli.string {
         Ok(s)
     } else if let Some(p) = cli.file {
+        if let Some(d) = p.parent() {
+            path.insert(0, d.to_string_lossy().into_owned());
+        }
         fs::read_to_string(p).map_err(Into::into)
     } else if cli.stdin {
         io::read_to_string(io::stdin()).map_err(Into::into)
@@ -67,31 +183,101 @@ fn main() {
         return;
     };
 
+    if cli.check {
+        let code = res.unwrap_or_else(|e| {
+            eprintln!("ERR:\n{e}");
+            std::process::exit(1);
+        });
+        let diags = check(&code, 1);
+        for d in &diags {
+            eprint!("error: {d}\n{}", EuLoc::new(&code, d.span).snippet());
+        }
+        if !diags.is_empty() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let ctx = EuEnvCtx::new(
-        EuEnvOpts { debug: cli.debug },
+        EuEnvOpts {
+            debug: cli.debug,
+            no_prelude: cli.no_prelude,
+            args,
+            path,
+            seed: cli.seed,
+            fuel: cli.fuel,
+            timeout: cli.timeout,
+            elem_budget: cli.elem_budget,
+            caps: cli.caps.into(),
+        },
         Arc::new(AtomicBool::new(true)),
         rand::rng(),
     );
 
-    match res
-        .map_err(Into::into)
-        .and_then(|code| EuEnv::apply_str(&code, &[], GenericHashMap::new(), &ctx))
-    {
+    let profile = cli.profile || cli.profile_folded.is_some();
+    let res = res.map_err(Into::into).and_then(|code| {
+        EuEnv::prelude(&ctx).and_then(|scope| {
+            if cli.debugger {
+                *ctx.hook.borrow_mut() = Some(Box::new(Debugger::new(&ctx, &code, &scope)?));
+            }
+            if profile {
+                *ctx.profile.borrow_mut() = Some(EuProfile::default());
+            }
+            ctx.reset_limits();
+            EuEnv::apply_str(&code, &[], scope, &ctx)
+        })
+    });
+
+    if let Some(p) = ctx.profile.borrow().as_ref() {
+        if cli.profile {
+            eprint!("{}", p.report());
+        }
+        if let Some(f) = cli.profile_folded
+            && let Err(e) = fs::write(f, p.folded())
+        {
+            eprintln!("ERR:\n{e}");
+        }
+    }
+
+    match res {
+        _ if let Some(code) = ctx.exit.get() => std::process::exit(code),
         Ok(env) => {
             if cli.debug || cli.dump {
                 println!("<< {env}");
             }
         }
         Err(e) => {
-            eprintln!("ERR:");
-            for c in e.0.chain() {
-                eprintln!("{c}");
-            }
+            eprint!("{}", e.report());
             std::process::exit(1);
         }
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn fmt(file: &path::Path, check: bool) {
+    let code = fs::read_to_string(file).unwrap_or_else(|e| {
+        eprintln!("ERR:\n{e}");
+        std::process::exit(1);
+    });
+    let out = pretty(&code).unwrap_or_else(|diags| {
+        for d in diags {
+            eprint!("error: {d}\n{}", EuLoc::new(&code, d.span).snippet());
+        }
+        std::process::exit(1);
+    });
+    if out == code {
+        return;
+    }
+    if check {
+        eprintln!("{} is not formatted", file.display());
+        std::process::exit(1);
+    }
+    if let Err(e) = fs::write(file, out) {
+        eprintln!("ERR:\n{e}");
+        std::process::exit(1);
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn repl() -> anyhow::Result<()> {
     let mut rl = DefaultEditor::new()?;
@@ -102,23 +288,38 @@ fn repl() -> anyhow::Result<()> {
         i.store(false, Ordering::SeqCst);
     })?;
 
-    let ctx = EuEnvCtx::new(EuEnvOpts { debug: false }, interrupt, rand::rng());
-    let mut env = EuEnv::new([], &[], GenericHashMap::new(), &ctx);
+    let ctx = EuEnvCtx::new(
+        EuEnvOpts {
+            debug: false,
+            no_prelude: false,
+            args: vec![],
+            path: vec![],
+            seed: None,
+            fuel: None,
+            timeout: None,
+            elem_budget: None,
+            caps: EuCaps::Full,
+        },
+        interrupt,
+        rand::rng(),
+    );
+    let mut env = EuEnv::new([], &[], EuEnv::prelude(&ctx)?, &ctx);
 
     loop {
         match rl.readline("euph> ") {
-            Ok(code) => match EuEnv::apply_str(&code, &[], env.scope.clone(), env.ctx) {
-                Ok(res) => {
-                    env = res;
-                    println!("{env}");
-                }
-                Err(e) => {
-                    eprintln!("ERR:");
-                    for c in e.0.chain() {
-                        eprintln!("{c}");
+            Ok(code) => {
+                ctx.reset_limits();
+                match EuEnv::apply_str(&code, &[], env.scope.clone(), env.ctx) {
+                    _ if let Some(code) = ctx.exit.get() => std::process::exit(code),
+                    Ok(res) => {
+                        env = res;
+                        println!("{env}");
+                    }
+                    Err(e) => {
+                        eprint!("{}", e.report());
                     }
                 }
-            },
+            }
             Err(ReadlineError::Interrupted) => {
                 println!("^C");
                 break;
@@ -136,3 +337,29 @@ fn repl() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args() {
+        let args = |argv: &[&str]| {
+            let mut cli = Cli::try_parse_from([&"euph"].into_iter().chain(argv)).unwrap();
+            (cli.args(), cli.file)
+        };
+        let prog = Some(path::PathBuf::from("prog.euph"));
+        assert_eq!(args(&["prog.euph"]), (vec![], prog.clone()));
+        assert_eq!(
+            args(&["prog.euph", "a", "--b"]),
+            (vec!["a".into(), "--b".into()], prog.clone())
+        );
+        assert_eq!(
+            args(&["--dump", "prog.euph", "--", "a"]),
+            (vec!["a".into()], prog)
+        );
+        assert_eq!(args(&["-s", "Args", "a", "b"]).0, ["a", "b"]);
+        assert_eq!(args(&["-s", "Args", "--", "a"]).0, ["a"]);
+        assert!(Cli::try_parse_from(["euph", "-s", "1", "-i"]).is_err());
+    }
+}
@@ -1,10 +1,8 @@
 use ecow::EcoVec;
 use winnow::{
     combinator::{
-        delimited,
         dispatch,
         fail,
-        opt,
         peek,
         preceded,
         repeat,
@@ -17,18 +15,22 @@ use winnow::{
 
 use crate::{
     parser::{
+        EuInput,
         bind::bind,
+        closed,
         raw::raw,
+        recover,
+        spanned,
         word_inner,
         ws,
     },
     types::EuSyn,
 };
 
-pub fn euphrates<'eu>(input: &mut &str) -> ModalResult<EcoVec<EuSyn<'eu>>> {
+pub fn euphrates<'eu>(input: &mut EuInput) -> ModalResult<EcoVec<EuSyn<'eu>>> {
     terminated(
-        repeat(0.., preceded(ws, syn)).fold(EcoVec::new, |mut ts, t| {
-            ts.push(t);
+        repeat(0.., preceded(ws, recover(syn))).fold(EcoVec::new, |mut ts, t| {
+            ts.extend(t);
             ts
         }),
         ws,
@@ -36,7 +38,7 @@ pub fn euphrates<'eu>(input: &mut &str) -> ModalResult<EcoVec<EuSyn<'eu>>> {
     .parse_next(input)
 }
 
-fn syn<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
+pub(super) fn syn<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
     dispatch!(peek(any);
         '[' => vec,
         ']' => fail,
@@ -56,34 +58,34 @@ fn syn<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
     .parse_next(input)
 }
 
-fn vec<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
-    delimited('[', euphrates, opt(']'))
-        .map(EuSyn::Vec)
+fn vec<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
+    spanned(closed("[", euphrates, "]"))
+        .map(|(ts, sp)| EuSyn::Vec(ts, sp))
         .parse_next(input)
 }
 
-fn map<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
-    delimited('{', euphrates, opt('}'))
-        .map(EuSyn::Map)
+fn map<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
+    spanned(closed("{", euphrates, "}"))
+        .map(|(ts, sp)| EuSyn::Map(ts, sp))
         .parse_next(input)
 }
 
-fn var<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
-    preceded('$', word_inner)
-        .map(EuSyn::Var)
+fn var<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
+    spanned(preceded('$', word_inner))
+        .map(|(w, sp)| EuSyn::Var(w, sp))
         .context(StrContext::Label("var"))
         .parse_next(input)
 }
 
-fn r#move<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
-    word_inner
-        .map(EuSyn::Move)
+fn r#move<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
+    spanned(word_inner)
+        .map(|(w, sp)| EuSyn::Move(w, sp))
         .context(StrContext::Label("move"))
         .parse_next(input)
 }
 
-fn get<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
-    preceded(('.', ws), word_inner)
-        .map(EuSyn::Get)
+fn get<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
+    spanned(preceded(('.', ws), word_inner))
+        .map(|(w, sp)| EuSyn::Get(w, sp))
         .parse_next(input)
 }
@@ -2,7 +2,6 @@ use ecow::EcoVec;
 use winnow::{
     combinator::{
         cut_err,
-        delimited,
         dispatch,
         opt,
         peek,
@@ -18,10 +17,14 @@ use winnow::{
 
 use crate::{
     parser::{
+        EuInput,
         char_inner,
+        closed,
         float_suffix,
         int_suffix,
         num_inner,
+        recover,
+        spanned,
         str_inner,
         str_raw_inner,
         word_inner,
@@ -33,17 +36,17 @@ use crate::{
     },
 };
 
-pub(super) fn bind<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
-    delimited('[', bind_inner, opt(']'))
-        .map(EuSyn::Bind)
+pub(super) fn bind<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
+    spanned(closed("[", bind_inner, "]"))
+        .map(|(bs, sp)| EuSyn::Bind(bs, sp))
         .context(StrContext::Label("bind"))
         .parse_next(input)
 }
 
-fn bind_inner<'eu>(input: &mut &str) -> ModalResult<EcoVec<EuBind<'eu>>> {
+fn bind_inner<'eu>(input: &mut EuInput) -> ModalResult<EcoVec<EuBind<'eu>>> {
     terminated(
-        repeat(0.., preceded(ws, syn)).fold(EcoVec::new, |mut ts, t| {
-            ts.push(t);
+        repeat(0.., preceded(ws, recover(syn))).fold(EcoVec::new, |mut ts, t| {
+            ts.extend(t);
             ts
         }),
         ws,
@@ -51,7 +54,7 @@ fn bind_inner<'eu>(input: &mut &str) -> ModalResult<EcoVec<EuBind<'eu>>> {
     .parse_next(input)
 }
 
-fn syn<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
+fn syn<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
     (
         dispatch!(peek(any);
             '`' => str_raw,
@@ -76,15 +79,15 @@ fn syn<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
         .parse_next(input)
 }
 
-fn str_raw<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
+fn str_raw<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
     str_raw_inner.map(EuBind::Str).parse_next(input)
 }
 
-fn str<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
+fn str<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
     str_inner.map(EuBind::Str).parse_next(input)
 }
 
-fn char<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
+fn char<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
     cut_err(
         char_inner
             .verify_map(|x| x.map(EuBind::Char))
@@ -93,26 +96,26 @@ fn char<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
     .parse_next(input)
 }
 
-fn union<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
-    delimited('(', bind_inner, opt(')'))
+fn union<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
+    closed("(", bind_inner, ")")
         .map(EuBind::Union)
         .context(StrContext::Label("union"))
         .parse_next(input)
 }
 
-fn vecz<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
-    delimited('[', bind_inner, opt(']'))
+fn vecz<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
+    closed("[", bind_inner, "]")
         .map(EuBind::Vecz)
         .parse_next(input)
 }
 
-fn map<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
-    delimited('{', bind_inner, opt('}'))
+fn map<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
+    closed("{", bind_inner, "}")
         .map(EuBind::Map)
         .parse_next(input)
 }
 
-fn num<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
+fn num<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
     let (is_float, ns) = num_inner.parse_next(input)?;
     if is_float {
         float_suffix(ns, EuBind::F64).parse_next(input)
@@ -121,26 +124,26 @@ fn num<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
     }
 }
 
-fn tag<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
-    cut_err(separated_pair(
+fn tag<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
+    cut_err(spanned(separated_pair(
         preceded('$', word_inner),
         ws,
-        delimited('(', bind_inner, opt(')')),
-    ))
-    .map(|(w, bs)| EuBind::Tag(w, bs))
+        closed("(", bind_inner, ")"),
+    )))
+    .map(|((w, bs), sp)| EuBind::Tag(w, bs, sp))
     .context(StrContext::Label("tag"))
     .parse_next(input)
 }
 
-fn bind_<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
+fn bind_<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
     preceded((ws, '\\', ws), syn)
         .context(StrContext::Label("bind"))
         .parse_next(input)
 }
 
-fn word<'eu>(input: &mut &str) -> ModalResult<EuBind<'eu>> {
-    word_inner
-        .map(EuBind::Word)
+fn word<'eu>(input: &mut EuInput) -> ModalResult<EuBind<'eu>> {
+    spanned(word_inner)
+        .map(|(w, sp)| EuBind::Word(w, sp))
         .context(StrContext::Label("word"))
         .parse_next(input)
 }
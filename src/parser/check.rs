@@ -0,0 +1,114 @@
+use std::{
+    cell::RefCell,
+    fmt,
+};
+
+use itertools::Itertools;
+use winnow::{
+    error::{
+        ContextError,
+        StrContext,
+    },
+    prelude::*,
+    stream::{
+        LocatingSlice,
+        Location,
+        Stateful,
+        Stream,
+    },
+};
+
+use crate::{
+    parser::{
+        EuState,
+        euphrates,
+    },
+    types::EuSpan,
+};
+
+/// A syntax problem found by [`check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EuDiag {
+    pub span: EuSpan,
+    pub msg: String,
+    pub expected: Vec<String>,
+}
+
+impl EuDiag {
+    pub(super) fn new(span: EuSpan, e: &ContextError, next: Option<char>) -> Self {
+        let msg = e
+            .context()
+            .find_map(|c| match c {
+                StrContext::Label(l) => Some(format!("invalid {l}")),
+                _ => None,
+            })
+            .unwrap_or_else(|| match next {
+                Some(c) => format!("unexpected `{c}`"),
+                None => "unexpected end of input".into(),
+            });
+        let expected = e
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Expected(v) => Some(v.to_string()),
+                _ => None,
+            })
+            .collect();
+        Self {
+            span,
+            msg,
+            expected,
+        }
+    }
+
+    pub(super) fn unclosed(span: EuSpan, open: &str, close: &str) -> Self {
+        Self {
+            span,
+            msg: format!("unclosed `{open}`"),
+            expected: vec![format!("`{close}`")],
+        }
+    }
+}
+
+impl fmt::Display for EuDiag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.msg)?;
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", self.expected.iter().join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `s` as source `src` without stopping at the first error, returning
+/// every syntax problem found in order of position.
+#[must_use]
+pub fn check(s: &str, src: u32) -> Vec<EuDiag> {
+    let diags = RefCell::new(vec![]);
+    let mut input = Stateful {
+        input: LocatingSlice::new(s),
+        state: EuState {
+            src,
+            diags: Some(&diags),
+        },
+    };
+    loop {
+        let start = input.checkpoint();
+        if euphrates.parse_next(&mut input).is_err() {
+            input.reset(&start);
+        }
+        let pos = input.current_token_start();
+        let Some(c) = input.next_token() else {
+            break;
+        };
+        diags.borrow_mut().push(EuDiag {
+            span: EuSpan::new(src, pos, input.current_token_start()),
+            msg: format!("unexpected `{c}`"),
+            expected: vec![],
+        });
+    }
+    let mut diags = diags.into_inner();
+    // Whitespace may be parsed more than once when backtracking.
+    diags.sort_by_key(|d| d.span.start);
+    diags.dedup_by(|a, b| a.span.start == b.span.start && a.msg == b.msg);
+    diags
+}
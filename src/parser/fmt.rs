@@ -0,0 +1,114 @@
+use winnow::{
+    ascii::digit1,
+    combinator::{
+        alt,
+        cut_err,
+        delimited,
+        dispatch,
+        empty,
+        opt,
+        peek,
+        preceded,
+        repeat,
+    },
+    error::{
+        StrContext,
+        StrContextValue,
+    },
+    prelude::*,
+    token::{
+        any,
+        one_of,
+        take_till,
+        take_while,
+    },
+};
+
+use crate::types::{
+    EuFmtAlign,
+    EuFmtArg,
+    EuFmtPiece,
+    EuFmtSpec,
+    EuFmtTy,
+};
+
+pub fn fmt_template<'i>(input: &mut &'i str) -> ModalResult<Vec<EuFmtPiece<'i>>> {
+    repeat(0.., piece).parse_next(input)
+}
+
+fn piece<'i>(input: &mut &'i str) -> ModalResult<EuFmtPiece<'i>> {
+    dispatch!(peek(any);
+        '{' => alt(("{{".value(EuFmtPiece::Lit("{")), arg)),
+        '}' => cut_err("}}")
+            .value(EuFmtPiece::Lit("}"))
+            .context(StrContext::Expected(StrContextValue::Description("`}}`"))),
+        _ => take_till(1.., ('{', '}')).map(EuFmtPiece::Lit),
+    )
+    .parse_next(input)
+}
+
+fn arg<'i>(input: &mut &'i str) -> ModalResult<EuFmtPiece<'i>> {
+    delimited(
+        '{',
+        cut_err((arg_ref, opt(preceded(':', spec))))
+            .map(|(a, s)| EuFmtPiece::Arg(a, s.unwrap_or_default())),
+        cut_err('}').context(StrContext::Expected(StrContextValue::CharLiteral('}'))),
+    )
+    .context(StrContext::Label("placeholder"))
+    .parse_next(input)
+}
+
+fn arg_ref<'i>(input: &mut &'i str) -> ModalResult<EuFmtArg<'i>> {
+    alt((
+        digit1.try_map(str::parse).map(EuFmtArg::Index),
+        take_while(1.., |c: char| c.is_alphanumeric() || c == '_').map(EuFmtArg::Name),
+        empty.value(EuFmtArg::Next),
+    ))
+    .parse_next(input)
+}
+
+fn spec(input: &mut &str) -> ModalResult<EuFmtSpec> {
+    (
+        opt(alt(((any, align), align.map(|a| (' ', a))))),
+        opt('+'),
+        opt('#'),
+        opt('0'),
+        opt(digit1.try_map(str::parse)),
+        opt(preceded('.', cut_err(digit1.try_map(str::parse)))),
+        opt(ty),
+    )
+        .map(|(fa, sign, alt, zero, width, prec, ty)| EuFmtSpec {
+            fill: fa.map_or(' ', |(c, _)| c),
+            align: fa.map(|(_, a)| a),
+            sign: sign.is_some(),
+            alt: alt.is_some(),
+            zero: zero.is_some(),
+            width,
+            prec,
+            ty: ty.unwrap_or(EuFmtTy::Display),
+        })
+        .context(StrContext::Label("format spec"))
+        .parse_next(input)
+}
+
+fn align(input: &mut &str) -> ModalResult<EuFmtAlign> {
+    dispatch!(one_of(('<', '^', '>'));
+        '<' => empty.value(EuFmtAlign::Left),
+        '^' => empty.value(EuFmtAlign::Center),
+        _ => empty.value(EuFmtAlign::Right),
+    )
+    .parse_next(input)
+}
+
+fn ty(input: &mut &str) -> ModalResult<EuFmtTy> {
+    dispatch!(one_of(('?', 'b', 'o', 'x', 'X', 'e', 'E'));
+        '?' => empty.value(EuFmtTy::Debug),
+        'b' => empty.value(EuFmtTy::Bin),
+        'o' => empty.value(EuFmtTy::Oct),
+        'x' => empty.value(EuFmtTy::LowerHex),
+        'X' => empty.value(EuFmtTy::UpperHex),
+        'e' => empty.value(EuFmtTy::LowerExp),
+        _ => empty.value(EuFmtTy::UpperExp),
+    )
+    .parse_next(input)
+}
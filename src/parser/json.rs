@@ -0,0 +1,172 @@
+use std::rc::Rc;
+
+use dashu_int::IBig;
+use hipstr::LocalHipStr;
+use ordered_float::OrderedFloat;
+use ordermap::OrderMap;
+use winnow::{
+    ascii::{
+        digit0,
+        digit1,
+        multispace0,
+    },
+    combinator::{
+        alt,
+        cut_err,
+        delimited,
+        dispatch,
+        empty,
+        fail,
+        opt,
+        peek,
+        preceded,
+        repeat,
+        separated,
+        separated_pair,
+        terminated,
+    },
+    error::{
+        StrContext,
+        StrContextValue,
+    },
+    prelude::*,
+    token::{
+        any,
+        one_of,
+        take_till,
+        take_while,
+    },
+};
+
+use crate::types::EuType;
+
+pub fn json<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+    delimited(multispace0, value, multispace0).parse_next(input)
+}
+
+fn value<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+    dispatch!(peek(any);
+        '{' => object,
+        '[' => array,
+        '"' => string.map(EuType::Str),
+        't' => "true".value(EuType::Bool(true)),
+        'f' => "false".value(EuType::Bool(false)),
+        'n' => "null".value(EuType::Opt(None)),
+        '-' | '0'..='9' => number,
+        _ => fail,
+    )
+    .context(StrContext::Expected(StrContextValue::Description(
+        "JSON value",
+    )))
+    .parse_next(input)
+}
+
+fn object<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+    delimited(
+        ('{', multispace0),
+        cut_err(separated(
+            0..,
+            separated_pair(
+                string.map(EuType::Str),
+                (multispace0, ':', multispace0),
+                value,
+            ),
+            (multispace0, ',', multispace0),
+        )),
+        cut_err((multispace0, '}')),
+    )
+    .map(|kvs: Vec<_>| EuType::Map(Rc::new(kvs.into_iter().collect::<OrderMap<_, _>>())))
+    .context(StrContext::Label("object"))
+    .parse_next(input)
+}
+
+fn array<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+    delimited(
+        ('[', multispace0),
+        cut_err(separated(0.., value, (multispace0, ',', multispace0))),
+        cut_err((multispace0, ']')),
+    )
+    .map(|ts: Vec<_>| EuType::vec(ts))
+    .context(StrContext::Label("array"))
+    .parse_next(input)
+}
+
+fn string<'eu>(input: &mut &str) -> ModalResult<LocalHipStr<'eu>> {
+    preceded(
+        '"',
+        cut_err(terminated(
+            repeat(
+                0..,
+                alt((
+                    take_till(1.., |c: char| c == '"' || c == '\\' || c < ' ')
+                        .map(LocalHipStr::from),
+                    escape.map(|c| LocalHipStr::from(c.to_string())),
+                )),
+            )
+            .fold(LocalHipStr::new, |mut s, p: LocalHipStr| {
+                s.push_str(&p);
+                s
+            }),
+            '"',
+        )),
+    )
+    .context(StrContext::Label("string"))
+    .parse_next(input)
+}
+
+fn escape(input: &mut &str) -> ModalResult<char> {
+    preceded(
+        '\\',
+        dispatch!(any;
+            '"' => empty.value('"'),
+            '\\' => empty.value('\\'),
+            '/' => empty.value('/'),
+            'b' => empty.value('\u{8}'),
+            'f' => empty.value('\u{c}'),
+            'n' => empty.value('\n'),
+            'r' => empty.value('\r'),
+            't' => empty.value('\t'),
+            'u' => unicode,
+            _ => fail,
+        ),
+    )
+    .context(StrContext::Label("escape"))
+    .parse_next(input)
+}
+
+fn unicode(input: &mut &str) -> ModalResult<char> {
+    let hex4 =
+        || take_while(4, |c: char| c.is_ascii_hexdigit()).try_map(|h| u32::from_str_radix(h, 16));
+    let hi = hex4().parse_next(input)?;
+    let c = if (0xd800..0xdc00).contains(&hi) {
+        let lo = preceded("\\u", hex4())
+            .verify(|lo| (0xdc00..0xe000).contains(lo))
+            .parse_next(input)?;
+        0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00)
+    } else {
+        hi
+    };
+    empty.verify_map(|()| char::from_u32(c)).parse_next(input)
+}
+
+fn number<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+    let ((_, _, frac, exp), ns) = (
+        opt('-'),
+        alt(("0", (one_of('1'..='9'), digit0).take())),
+        opt(('.', digit1)),
+        opt((one_of(('e', 'E')), opt(one_of(('+', '-'))), digit1)),
+    )
+        .with_taken()
+        .parse_next(input)?;
+    if frac.is_some() || exp.is_some() {
+        empty
+            .try_map(|()| ns.parse().map(|n: f64| EuType::F64(OrderedFloat(n))))
+            .parse_next(input)
+    } else if let Ok(n) = ns.parse() {
+        Ok(EuType::I64(n))
+    } else {
+        empty
+            .try_map(|()| ns.parse().map(|n: IBig| EuType::IBig(n)))
+            .parse_next(input)
+    }
+}
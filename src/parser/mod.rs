@@ -1,17 +1,29 @@
 mod base;
 mod bind;
+mod check;
+mod fmt;
+mod json;
+mod pretty;
 mod raw;
 
+use std::cell::RefCell;
+
 pub use base::euphrates;
+pub use check::*;
 use dashu_int::IBig;
+pub use fmt::fmt_template;
 use hipstr::LocalHipStr;
+pub use json::json;
 use ordered_float::OrderedFloat;
+pub use pretty::pretty;
 use winnow::{
-    ascii::digit1,
+    ascii::{
+        digit1,
+        till_line_ending,
+    },
     combinator::{
         alt,
         cut_err,
-        delimited,
         dispatch,
         empty,
         fail,
@@ -20,28 +32,163 @@ use winnow::{
         peek,
         preceded,
         repeat,
+        terminated,
     },
     error::{
+        ContextError,
         StrContext,
         StrContextValue,
     },
     prelude::*,
+    stream::{
+        LocatingSlice,
+        Location,
+        Stateful,
+    },
     token::{
         any,
+        none_of,
         one_of,
+        take_till,
         take_while,
     },
 };
 
-fn str_raw_inner<'eu>(input: &mut &str) -> ModalResult<LocalHipStr<'eu>> {
-    delimited('`', take_while(0.., |c| c != '`'), opt('`'))
+use crate::types::EuSpan;
+
+/// Parser input tagged with the id of the source it is read from.
+pub type EuInput<'i> = Stateful<LocatingSlice<&'i str>, EuState<'i>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EuState<'i> {
+    pub src: u32,
+    /// Where to collect diagnostics when parsing in checking mode.
+    pub diags: Option<&'i RefCell<Vec<EuDiag>>>,
+}
+
+#[must_use]
+pub fn input(s: &str, src: u32) -> EuInput<'_> {
+    Stateful {
+        input: LocatingSlice::new(s),
+        state: EuState { src, diags: None },
+    }
+}
+
+/// Like `delimited(open, p, opt(close))`, but a missing `close` is reported
+/// in checking mode.
+fn closed<'i, O>(
+    open: &'static str,
+    p: impl ModalParser<EuInput<'i>, O, ContextError>,
+    close: &'static str,
+) -> impl FnMut(&mut EuInput<'i>) -> ModalResult<O> {
+    enclosed(open, p, close, false)
+}
+
+/// Like [`closed`], but a missing `close` is an error outside checking mode.
+fn closed_strict<'i, O>(
+    open: &'static str,
+    p: impl ModalParser<EuInput<'i>, O, ContextError>,
+    close: &'static str,
+) -> impl FnMut(&mut EuInput<'i>) -> ModalResult<O> {
+    enclosed(open, p, close, true)
+}
+
+fn enclosed<'i, O>(
+    mut open: &'static str,
+    mut p: impl ModalParser<EuInput<'i>, O, ContextError>,
+    close: &'static str,
+    strict: bool,
+) -> impl FnMut(&mut EuInput<'i>) -> ModalResult<O> {
+    move |input| {
+        let start = input.current_token_start();
+        open.parse_next(input)?;
+        let o = p.parse_next(input)?;
+        if opt(close).parse_next(input)?.is_none() {
+            match input.state.diags {
+                Some(diags) => diags.borrow_mut().push(EuDiag::unclosed(
+                    EuSpan::new(input.state.src, start, start + open.len()),
+                    open,
+                    close,
+                )),
+                None if strict => {
+                    return cut_err(fail)
+                        .context(StrContext::Expected(StrContextValue::StringLiteral(close)))
+                        .parse_next(input);
+                }
+                None => {}
+            }
+        }
+        Ok(o)
+    }
+}
+
+/// In checking mode, reports a failure of `p` and skips past the offending
+/// token instead of failing, so that parsing can carry on.
+fn recover<'i, O>(
+    mut p: impl ModalParser<EuInput<'i>, O, ContextError>,
+) -> impl FnMut(&mut EuInput<'i>) -> ModalResult<Option<O>> {
+    move |input| {
+        let Some(diags) = input.state.diags else {
+            return p.parse_next(input).map(Some);
+        };
+        let start = input.checkpoint();
+        let pos = input.current_token_start();
+        match p.parse_next(input) {
+            Ok(o) => Ok(Some(o)),
+            Err(e) => {
+                input.reset(&start);
+                let next = input.chars().next();
+                if next.is_none_or(|c| matches!(c, ']' | ')' | '}')) {
+                    return Err(e);
+                }
+                let e = e.into_inner().unwrap_or_default();
+                skip_token(input);
+                diags.borrow_mut().push(EuDiag::new(
+                    EuSpan::new(input.state.src, pos, input.current_token_start()),
+                    &e,
+                    next,
+                ));
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn skip_token(input: &mut EuInput) {
+    let _: ModalResult<()> = alt((
+        (
+            '"',
+            repeat::<_, _, (), _, _>(0.., alt((preceded('\\', any), none_of('"')))),
+            opt('"'),
+        )
+            .void(),
+        (any, take_while(0.., |c| c == '.' || is_word_char(c))).void(),
+    ))
+    .parse_next(input);
+}
+
+fn spanned<'i, O>(
+    mut p: impl ModalParser<EuInput<'i>, O, ContextError>,
+) -> impl FnMut(&mut EuInput<'i>) -> ModalResult<(O, EuSpan)> {
+    move |input| {
+        let start = input.current_token_start();
+        let o = p.parse_next(input)?;
+        Ok((
+            o,
+            EuSpan::new(input.state.src, start, input.previous_token_end()),
+        ))
+    }
+}
+
+fn str_raw_inner<'eu>(input: &mut EuInput) -> ModalResult<LocalHipStr<'eu>> {
+    closed("`", take_while(0.., |c| c != '`'), "`")
         .output_into()
         .parse_next(input)
 }
 
-fn str_inner<'eu>(input: &mut &str) -> ModalResult<LocalHipStr<'eu>> {
-    delimited(
-        '"',
+fn str_inner<'eu>(input: &mut EuInput) -> ModalResult<LocalHipStr<'eu>> {
+    closed(
+        "\"",
         repeat(0.., dispatch!(peek(any); '"' => fail, _ => char_atom)).fold(
             LocalHipStr::new,
             |mut s, co| {
@@ -51,16 +198,16 @@ fn str_inner<'eu>(input: &mut &str) -> ModalResult<LocalHipStr<'eu>> {
                 s
             },
         ),
-        opt('"'),
+        "\"",
     )
     .parse_next(input)
 }
 
-fn char_inner(input: &mut &str) -> ModalResult<Option<char>> {
+fn char_inner(input: &mut EuInput) -> ModalResult<Option<char>> {
     preceded('\'', char_atom).parse_next(input)
 }
 
-fn char_atom(input: &mut &str) -> ModalResult<Option<char>> {
+fn char_atom(input: &mut EuInput) -> ModalResult<Option<char>> {
     dispatch!(any;
         '\\' => dispatch!(cut_err(any).context(StrContext::Label("escape"));
             '\n' => empty.value(None),
@@ -77,7 +224,7 @@ fn char_atom(input: &mut &str) -> ModalResult<Option<char>> {
     .parse_next(input)
 }
 
-fn char_hex(input: &mut &str) -> ModalResult<char> {
+fn char_hex(input: &mut EuInput) -> ModalResult<char> {
     cut_err(
         take_while(2, |c: char| c.is_ascii_hexdigit())
             .try_map(|hex| u8::from_str_radix(hex, 16))
@@ -90,19 +237,15 @@ fn char_hex(input: &mut &str) -> ModalResult<char> {
     .parse_next(input)
 }
 
-fn char_uni(input: &mut &str) -> ModalResult<char> {
+fn char_uni(input: &mut EuInput) -> ModalResult<char> {
     cut_err(
-        delimited(
-            '{',
-            take_while(1..=6, |c: char| c.is_ascii_hexdigit()),
-            opt('}'),
-        )
-        .context(StrContext::Label("unicode escape"))
-        .context(StrContext::Expected(
-            "`\\u{H...}` where `H...` is 1-6 hexadecimal digits".into(),
-        ))
-        .try_map(|hex| u32::from_str_radix(hex, 16))
-        .verify_map(char::from_u32),
+        closed("{", take_while(1..=6, |c: char| c.is_ascii_hexdigit()), "}")
+            .context(StrContext::Label("unicode escape"))
+            .context(StrContext::Expected(
+                "`\\u{H...}` where `H...` is 1-6 hexadecimal digits".into(),
+            ))
+            .try_map(|hex| u32::from_str_radix(hex, 16))
+            .verify_map(char::from_u32),
     )
     .context(StrContext::Label("unicode escape"))
     .context(StrContext::Expected(
@@ -117,7 +260,7 @@ fn int_suffix<T>(
     i64: impl FnOnce(i64) -> T + Copy,
     ibig: impl FnOnce(IBig) -> T + Copy,
     f64: impl FnOnce(OrderedFloat<f64>) -> T + Copy,
-) -> impl FnMut(&mut &str) -> ModalResult<T> {
+) -> impl FnMut(&mut EuInput) -> ModalResult<T> {
     move |input| {
         cut_err(alt((
             "i32"
@@ -143,7 +286,7 @@ fn int_suffix<T>(
 fn float_suffix<T>(
     ns: &str,
     f64: impl FnOnce(OrderedFloat<f64>) -> T + Copy,
-) -> impl FnMut(&mut &str) -> ModalResult<T> {
+) -> impl FnMut(&mut EuInput) -> ModalResult<T> {
     move |input| {
         not_int_suffix(input)?;
         cut_err(alt((
@@ -158,7 +301,7 @@ fn float_suffix<T>(
     }
 }
 
-fn not_int_suffix(input: &mut &str) -> ModalResult<()> {
+fn not_int_suffix(input: &mut EuInput) -> ModalResult<()> {
     cut_err(not(alt(("i32", "i64", "ibig"))))
         .context(StrContext::Label("float suffix"))
         .context(StrContext::Expected(StrContextValue::Description(
@@ -167,7 +310,7 @@ fn not_int_suffix(input: &mut &str) -> ModalResult<()> {
         .parse_next(input)
 }
 
-fn num_inner<'i>(input: &mut &'i str) -> ModalResult<(bool, &'i str)> {
+fn num_inner<'i>(input: &mut EuInput<'i>) -> ModalResult<(bool, &'i str)> {
     let ((_, dec, exp), ns) = (
         digit1,
         opt(preceded('.', digit1)),
@@ -181,7 +324,7 @@ fn num_inner<'i>(input: &mut &'i str) -> ModalResult<(bool, &'i str)> {
     Ok((dec.is_some() || exp.is_some(), ns))
 }
 
-fn word_inner<'eu>(input: &mut &str) -> ModalResult<LocalHipStr<'eu>> {
+fn word_inner<'eu>(input: &mut EuInput) -> ModalResult<LocalHipStr<'eu>> {
     (
         take_while(1, |c: char| {
             !c.is_dec_digit() && !matches!(c, '$' | '\\') && is_word_char(c)
@@ -195,12 +338,39 @@ fn word_inner<'eu>(input: &mut &str) -> ModalResult<LocalHipStr<'eu>> {
 fn is_word_char(c: char) -> bool {
     !matches!(
         c,
-        '`' | '"' | '\'' | '(' | ')' | '[' | ']' | '{' | '}' | '.' | '\\'
+        '`' | '"' | '\'' | '(' | ')' | '[' | ']' | '{' | '}' | '.' | '\\' | ';'
     ) && !c.is_whitespace()
 }
 
-fn ws<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
-    take_while(0.., char::is_whitespace).parse_next(input)
+fn ws<'i>(input: &mut EuInput<'i>) -> ModalResult<&'i str> {
+    repeat::<_, _, (), _, _>(
+        0..,
+        alt((
+            take_while(1.., char::is_whitespace),
+            block_comment,
+            (';', till_line_ending).take(),
+        )),
+    )
+    .take()
+    .parse_next(input)
+}
+
+fn block_comment<'i>(input: &mut EuInput<'i>) -> ModalResult<&'i str> {
+    closed_strict(
+        ";(",
+        repeat::<_, _, (), _, _>(
+            0..,
+            alt((
+                block_comment,
+                take_till(1.., ';'),
+                terminated(';', not(')')).take(),
+            )),
+        ),
+        ";)",
+    )
+    .take()
+    .context(StrContext::Label("block comment"))
+    .parse_next(input)
 }
 
 #[cfg(test)]
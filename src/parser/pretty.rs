@@ -0,0 +1,121 @@
+use winnow::{
+    ascii::till_line_ending,
+    combinator::alt,
+    prelude::*,
+    token::{
+        any,
+        take_while,
+    },
+};
+
+use crate::{
+    parser::{
+        EuDiag,
+        EuInput,
+        base::syn,
+        block_comment,
+        check,
+        input,
+        ws,
+    },
+    types::EuSpan,
+};
+
+/// Formats source canonically. Tokens are normalized, whitespace between them
+/// is collapsed and nesting is indented, while comments and line breaks are
+/// kept. Tokens spanning several lines or containing comments are kept as
+/// written.
+pub fn pretty(s: &str) -> Result<String, Vec<EuDiag>> {
+    let diags = check(s, 0);
+    if !diags.is_empty() {
+        return Err(diags);
+    }
+    let mut out = String::new();
+    seq(&mut input(s, 0), &mut out, 0).map_err(|e| {
+        vec![EuDiag::new(
+            EuSpan::default(),
+            &e.into_inner().unwrap_or_default(),
+            None,
+        )]
+    })?;
+    Ok(out)
+}
+
+fn seq(input: &mut EuInput, out: &mut String, depth: usize) -> ModalResult<()> {
+    let mut first = true;
+    loop {
+        let g = ws.parse_next(input)?;
+        let Some(c) = input
+            .chars()
+            .next()
+            .filter(|c| !matches!(c, ']' | ')' | '}'))
+        else {
+            return gap(out, g, depth, first, true);
+        };
+        gap(out, g, depth, first, false)?;
+        first = false;
+        if let Some(mut close) = match c {
+            '(' => Some(')'),
+            '[' => Some(']'),
+            '{' => Some('}'),
+            _ => None,
+        } {
+            out.push(any.parse_next(input)?);
+            seq(input, out, depth + 1)?;
+            out.push(close.parse_next(input)?);
+        } else {
+            let (t, raw) = syn.with_taken().parse_next(input)?;
+            if raw.contains(['\n', ';']) {
+                out.push_str(raw);
+            } else {
+                out.push_str(&t.to_source());
+            }
+        }
+    }
+}
+
+/// Writes the whitespace and comments `g` found before a token, or before the
+/// end of a block if `last`.
+fn gap(out: &mut String, g: &str, depth: usize, mut first: bool, last: bool) -> ModalResult<()> {
+    let mut g = input(g, 0);
+    let mut nl = 0;
+    while !g.is_empty() {
+        let w = take_while(0.., char::is_whitespace).parse_next(&mut g)?;
+        nl += w.matches('\n').count();
+        if g.is_empty() {
+            break;
+        }
+        let c = alt((block_comment, (';', till_line_ending).take())).parse_next(&mut g)?;
+        sep(out, nl, depth, first);
+        out.push_str(c);
+        nl = 0;
+        first = false;
+    }
+    if !last {
+        sep(out, nl, depth, first);
+    } else if depth == 0 {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+    } else if nl > 0 {
+        sep(out, 1, depth - 1, true);
+    }
+    Ok(())
+}
+
+fn sep(out: &mut String, nl: usize, depth: usize, first: bool) {
+    if out.is_empty() {
+        return;
+    }
+    match nl {
+        0 if first => {}
+        0 => out.push(' '),
+        _ => {
+            out.push('\n');
+            if nl > 1 {
+                out.push('\n');
+            }
+            out.push_str(&"  ".repeat(depth));
+        }
+    }
+}
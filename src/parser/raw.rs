@@ -1,10 +1,8 @@
 use winnow::{
     combinator::{
         cut_err,
-        delimited,
         dispatch,
         fail,
-        opt,
         peek,
     },
     error::StrContext,
@@ -14,11 +12,14 @@ use winnow::{
 
 use crate::{
     parser::{
+        EuInput,
         char_inner,
+        closed,
         euphrates,
         float_suffix,
         int_suffix,
         num_inner,
+        spanned,
         str_inner,
         str_raw_inner,
         word_inner,
@@ -29,11 +30,13 @@ use crate::{
     },
 };
 
-pub(super) fn raw<'eu>(input: &mut &str) -> ModalResult<EuSyn<'eu>> {
-    raw_inner.map(EuSyn::Raw).parse_next(input)
+pub(super) fn raw<'eu>(input: &mut EuInput) -> ModalResult<EuSyn<'eu>> {
+    spanned(raw_inner)
+        .map(|(t, sp)| EuSyn::Raw(t, sp))
+        .parse_next(input)
 }
 
-pub(super) fn raw_inner<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+pub(super) fn raw_inner<'eu>(input: &mut EuInput) -> ModalResult<EuType<'eu>> {
     dispatch!(peek(any);
         '(' => expr,
         ')' => fail,
@@ -46,22 +49,22 @@ pub(super) fn raw_inner<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
     .parse_next(input)
 }
 
-fn expr<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
-    delimited('(', euphrates, opt(')'))
+fn expr<'eu>(input: &mut EuInput) -> ModalResult<EuType<'eu>> {
+    closed("(", euphrates, ")")
         .map(EuType::expr)
         .context(StrContext::Label("expr"))
         .parse_next(input)
 }
 
-fn str_raw<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+fn str_raw<'eu>(input: &mut EuInput) -> ModalResult<EuType<'eu>> {
     str_raw_inner.map(EuType::Str).parse_next(input)
 }
 
-fn str<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+fn str<'eu>(input: &mut EuInput) -> ModalResult<EuType<'eu>> {
     str_inner.map(EuType::Str).parse_next(input)
 }
 
-fn char<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+fn char<'eu>(input: &mut EuInput) -> ModalResult<EuType<'eu>> {
     cut_err(
         char_inner
             .verify_map(|x| x.map(EuType::Char))
@@ -70,7 +73,7 @@ fn char<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
     .parse_next(input)
 }
 
-fn num<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
+fn num<'eu>(input: &mut EuInput) -> ModalResult<EuType<'eu>> {
     let (is_float, ns) = num_inner.parse_next(input)?;
     if is_float {
         float_suffix(ns, EuType::F64).parse_next(input)
@@ -79,9 +82,9 @@ fn num<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
     }
 }
 
-fn word<'eu>(input: &mut &str) -> ModalResult<EuType<'eu>> {
-    word_inner
-        .map(EuType::Word)
+fn word<'eu>(input: &mut EuInput) -> ModalResult<EuType<'eu>> {
+    spanned(word_inner)
+        .map(|(w, sp)| EuType::Word(w, sp))
         .context(StrContext::Label("word"))
         .parse_next(input)
 }
@@ -46,7 +46,7 @@ fn word() {
 
 #[test]
 fn var() {
-    assert_eq!(parse("$asdf"), Ok(eco_vec![EuSyn::Var("asdf".into())]));
+    assert_eq!(parse("$asdf"), Ok(eco_vec![EuSyn::var("asdf")]));
     assert!(is_err("$"));
     assert!(is_err("$$"));
     assert!(is_err("$\\"));
@@ -55,7 +55,7 @@ fn var() {
 
 #[test]
 fn r#move() {
-    assert_eq!(parse("\\asdf"), Ok(eco_vec![EuSyn::Move("asdf".into())]));
+    assert_eq!(parse("\\asdf"), Ok(eco_vec![EuSyn::r#move("asdf")]));
     assert!(is_err("\\"));
     assert!(is_err("\\\\"));
     assert!(is_err("\\$"));
@@ -70,7 +70,7 @@ fn dec() {
 
 #[test]
 fn get() {
-    assert_eq!(parse(".asdf"), Ok(eco_vec![EuSyn::Get("asdf".into())]));
+    assert_eq!(parse(".asdf"), Ok(eco_vec![EuSyn::get("asdf")]));
 }
 
 #[test]
@@ -204,10 +204,10 @@ fn expr_invalid() {
 
 #[test]
 fn vec() {
-    assert_eq!(parse("[]"), Ok(eco_vec![EuSyn::Vec(eco_vec![])]));
+    assert_eq!(parse("[]"), Ok(eco_vec![EuSyn::vec(eco_vec![])]));
     assert_eq!(
         parse(r#"[1 "2" 3+ asdf]"#),
-        Ok(eco_vec![EuSyn::Vec(eco_vec![
+        Ok(eco_vec![EuSyn::vec(eco_vec![
             EuType::ibig(1).into(),
             EuType::str("2").into(),
             EuType::ibig(3).into(),
@@ -217,7 +217,7 @@ fn vec() {
     );
     assert_eq!(
         parse(r#"[1 "2" 3+ asdf"#),
-        Ok(eco_vec![EuSyn::Vec(eco_vec![
+        Ok(eco_vec![EuSyn::vec(eco_vec![
             EuType::ibig(1).into(),
             EuType::str("2").into(),
             EuType::ibig(3).into(),
@@ -227,11 +227,11 @@ fn vec() {
     );
     assert_eq!(
         parse(r#"[[1 "2"] 3+ [asdf]]"#),
-        Ok(eco_vec![EuSyn::Vec(eco_vec![
-            EuSyn::Vec(eco_vec![EuType::ibig(1).into(), EuType::str("2").into()]),
+        Ok(eco_vec![EuSyn::vec(eco_vec![
+            EuSyn::vec(eco_vec![EuType::ibig(1).into(), EuType::str("2").into()]),
             EuType::ibig(3).into(),
             EuType::word("+").into(),
-            EuSyn::Vec(eco_vec![EuType::word("asdf").into()])
+            EuSyn::vec(eco_vec![EuType::word("asdf").into()])
         ])])
     );
 }
@@ -294,3 +294,40 @@ fn all() {
     );
     assert!(is_err("12e3.4"));
 }
+
+#[test]
+fn comment() {
+    assert_eq!(parse("; only a comment"), Ok(eco_vec![]));
+    assert_eq!(
+        parse("a ; b\nc;d"),
+        Ok(eco_vec![EuType::word("a").into(), EuType::word("c").into()])
+    );
+    assert_eq!(
+        parse("a ;( b ;( c ;) d ;) e"),
+        Ok(eco_vec![EuType::word("a").into(), EuType::word("e").into()])
+    );
+    assert!(is_err("a;(b"));
+    assert!(is_err("a ;( b ;( c ;) d"));
+    assert_eq!(
+        parse("(a ; b\n)"),
+        Ok(eco_vec![
+            EuType::expr(eco_vec![EuType::word("a").into()]).into()
+        ])
+    );
+    assert_eq!(
+        parse(r#""; not a comment""#),
+        Ok(eco_vec![EuType::str("; not a comment").into()])
+    );
+}
+
+#[test]
+fn spans() {
+    assert_eq!(
+        parse(r#"12 "s" [a 3] {b} (c) $d"#)
+            .unwrap()
+            .iter()
+            .map(|t| (t.span().start, t.span().end))
+            .collect::<Vec<_>>(),
+        [(0, 2), (3, 6), (7, 12), (13, 16), (17, 20), (21, 23)]
+    );
+}
@@ -0,0 +1,60 @@
+use crate::parser::check;
+
+fn diags(s: &str) -> Vec<(usize, usize, String)> {
+    check(s, 0)
+        .into_iter()
+        .map(|d| (d.span.start, d.span.end, d.to_string()))
+        .collect()
+}
+
+#[test]
+fn valid() {
+    assert_eq!(diags(""), []);
+    assert_eq!(diags(r#"1 2 + [a "b" 'c] \[x (1 2)] ;( ok ;)"#), []);
+}
+
+#[test]
+fn unclosed() {
+    assert_eq!(
+        diags("[1 (2 {3"),
+        [
+            (0, 1, "unclosed `[`, expected `]`".into()),
+            (3, 4, "unclosed `(`, expected `)`".into()),
+            (6, 7, "unclosed `{`, expected `}`".into()),
+        ]
+    );
+    assert_eq!(
+        diags(r#"a "bc"#),
+        [(2, 3, "unclosed `\"`, expected `\"`".into())]
+    );
+    assert_eq!(
+        diags("a ;( b"),
+        [(2, 4, "unclosed `;(`, expected `;)`".into())]
+    );
+    assert_eq!(
+        diags(r"\[a $Foo(b]"),
+        [(8, 9, "unclosed `(`, expected `)`".into())]
+    );
+}
+
+#[test]
+fn recovers() {
+    assert_eq!(
+        diags(r#"1 $ 2 3.5i32 "\xZZ" ) 4"#),
+        [
+            (2, 3, "invalid var".into()),
+            (
+                6,
+                12,
+                "invalid float suffix, expected `f64` or no suffix".into()
+            ),
+            (
+                13,
+                19,
+                "invalid hex pair escape, expected `\\xHH` where `H` is a hexadecimal digit".into()
+            ),
+            (20, 21, "unexpected `)`".into()),
+        ]
+    );
+    assert_eq!(diags(r"\[a . b]"), [(4, 5, "invalid word".into())]);
+}
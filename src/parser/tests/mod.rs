@@ -1,5 +1,7 @@
 mod base;
 mod bind;
+mod check;
+mod pretty;
 
 use ecow::EcoVec;
 use winnow::{
@@ -11,14 +13,18 @@ use winnow::{
 };
 
 use crate::{
-    parser::euphrates,
+    parser::{
+        EuInput,
+        euphrates,
+        input,
+    },
     types::EuSyn,
 };
 
-fn parse(input: &str) -> Result<EcoVec<EuSyn<'_>>, ParseError<&str, ContextError>> {
-    euphrates.parse(input)
+fn parse(s: &str) -> Result<EcoVec<EuSyn<'_>>, ParseError<EuInput<'_>, ContextError>> {
+    euphrates.parse(input(s, 0))
 }
 
-fn is_err(input: &str) -> bool {
-    euphrates.parse(input).is_err()
+fn is_err(s: &str) -> bool {
+    euphrates.parse(input(s, 0)).is_err()
 }
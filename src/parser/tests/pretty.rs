@@ -0,0 +1,44 @@
+use super::*;
+use crate::parser::pretty;
+
+fn round_trip(s: &str) -> String {
+    let ts = parse(s).unwrap();
+    let out = EuSyn::fmt_source(&ts);
+    assert_eq!(format!("{:?}", parse(&out).unwrap()), format!("{ts:?}"));
+    out
+}
+
+#[test]
+fn source() {
+    assert_eq!(
+        round_trip("1 2i32 3i64 4.50 5f64 1e20"),
+        "1 2i32 3i64 4.5 5.0 1e20"
+    );
+    assert_eq!(
+        round_trip(r#""a\"b\n" `c\d` 'x '\\ '\n ' "#),
+        r#""a\"b\n" "c\\d" 'x '\\ '\n ' "#
+    );
+    assert_eq!(
+        round_trip("$a \\b . c [1 {2 3}] (d (e))"),
+        "$a \\b .c [1 {2 3}] (d (e))"
+    );
+    assert_eq!(
+        round_trip(r#"\[a "b" 1\c ($Some(d) e) [f] {g h}]"#),
+        r#"\[a "b" 1\c ($Some(d) e) [f] {g h}]"#
+    );
+}
+
+#[test]
+fn layout() {
+    assert_eq!(pretty(""), Ok(String::new()));
+    assert_eq!(
+        pretty("\n; doc\n0 1(dup  tuck + f )\\[ f ]  f\n\n\n[ 1\n    2 ]"),
+        Ok("; doc\n0 1 (dup tuck + f) \\[f] f\n\n[1\n  2]\n".into())
+    );
+    assert_eq!(
+        pretty("(\n a ;( x ;)\n   (b ; y\n c)\n)"),
+        Ok("(\n  a ;( x ;)\n  (b ; y\n    c)\n)\n".into())
+    );
+    assert_eq!(pretty("\"a\nb\"  `c`"), Ok("\"a\nb\" \"c\"\n".into()));
+    assert!(pretty("[1 2").is_err());
+}
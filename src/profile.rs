@@ -0,0 +1,198 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    rc::Rc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use itertools::Itertools;
+
+/// Call counts and timings per word, collected while a context built with
+/// [`crate::env::EuEnvCtx::with_profile`] evaluates.
+#[derive(Debug, Default)]
+pub struct EuProfile {
+    /// Word names, indexed by the ids used everywhere else.
+    names: Vec<Rc<str>>,
+    ids: HashMap<Rc<str>, usize>,
+    /// Stats per word id.
+    words: Vec<EuWordStats>,
+    /// Open frames per word id, to tell recursive calls apart.
+    open: Vec<usize>,
+    /// Every stack of words seen, as a tree keyed by parent node and word id.
+    nodes: Vec<EuProfNode>,
+    edges: HashMap<(Option<usize>, usize), usize>,
+    frames: Vec<EuProfFrame>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EuWordStats {
+    pub calls: u64,
+    /// Time until the word returned, counted once for recursive calls.
+    pub incl: Duration,
+    /// Time not spent in other words.
+    pub excl: Duration,
+}
+
+#[derive(Debug)]
+struct EuProfNode {
+    parent: Option<usize>,
+    word: usize,
+    /// Exclusive time spent in this stack.
+    excl: Duration,
+}
+
+#[derive(Debug)]
+struct EuProfFrame {
+    word: usize,
+    node: usize,
+    start: Instant,
+    children: Duration,
+}
+
+impl EuProfile {
+    /// Starts timing a call to `name`, returning the frame to pass to
+    /// [`Self::exit`].
+    pub fn enter(&mut self, name: &str) -> usize {
+        let word = if let Some(&id) = self.ids.get(name) {
+            id
+        } else {
+            let name: Rc<str> = name.into();
+            self.names.push(name.clone());
+            self.words.push(EuWordStats::default());
+            self.open.push(0);
+            self.ids.insert(name, self.names.len() - 1);
+            self.names.len() - 1
+        };
+        let parent = self.frames.last().map(|f| f.node);
+        let node = *self.edges.entry((parent, word)).or_insert_with(|| {
+            self.nodes.push(EuProfNode {
+                parent,
+                word,
+                excl: Duration::ZERO,
+            });
+            self.nodes.len() - 1
+        });
+        self.open[word] += 1;
+        self.frames.push(EuProfFrame {
+            word,
+            node,
+            start: Instant::now(),
+            children: Duration::ZERO,
+        });
+        self.frames.len() - 1
+    }
+
+    /// Finishes timing `frame` along with any frames still open above it.
+    pub fn exit(&mut self, frame: usize) {
+        while self.frames.len() > frame {
+            let Some(f) = self.frames.pop() else {
+                break;
+            };
+            let incl = f.start.elapsed();
+            let excl = incl.saturating_sub(f.children);
+            self.nodes[f.node].excl += excl;
+            self.open[f.word] -= 1;
+            let stats = &mut self.words[f.word];
+            stats.calls += 1;
+            stats.excl += excl;
+            if self.open[f.word] == 0 {
+                stats.incl += incl;
+            }
+            if let Some(g) = self.frames.last_mut() {
+                g.children += incl;
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn word(&self, name: &str) -> Option<EuWordStats> {
+        self.ids.get(name).map(|&id| self.words[id])
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, EuWordStats)> {
+        self.names
+            .iter()
+            .map(|n| &**n)
+            .zip(self.words.iter().copied())
+    }
+
+    /// Exclusive time spent in each stack of words, innermost last.
+    pub fn stacks(&self) -> impl Iterator<Item = (Vec<&str>, Duration)> {
+        self.nodes.iter().map(|node| {
+            let mut path = vec![&*self.names[node.word]];
+            let mut parent = node.parent;
+            while let Some(p) = parent {
+                path.push(&self.names[self.nodes[p].word]);
+                parent = self.nodes[p].parent;
+            }
+            path.reverse();
+            (path, node.excl)
+        })
+    }
+
+    /// A table of words sorted by exclusive time.
+    #[must_use]
+    pub fn report(&self) -> String {
+        let mut s = format!(
+            "{:>10} {:>12} {:>12}  word\n",
+            "calls", "incl ms", "excl ms"
+        );
+        for (w, st) in self
+            .words()
+            .sorted_by(|(a, x), (b, y)| y.excl.cmp(&x.excl).then_with(|| a.cmp(b)))
+        {
+            _ = writeln!(
+                s,
+                "{:>10} {:>12.3} {:>12.3}  {w}",
+                st.calls,
+                st.incl.as_secs_f64() * 1e3,
+                st.excl.as_secs_f64() * 1e3,
+            );
+        }
+        s
+    }
+
+    /// Stacks in the folded format read by flamegraph tools, weighted in
+    /// microseconds.
+    #[must_use]
+    pub fn folded(&self) -> String {
+        self.stacks()
+            .map(|(path, d)| format!("{} {}\n", path.join(";"), d.as_micros()))
+            .sorted()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames() {
+        let mut p = EuProfile::default();
+        let f = p.enter("f");
+        let g = p.enter("g");
+        p.exit(g);
+        let f1 = p.enter("f");
+        p.enter("+");
+        p.exit(f1);
+        p.exit(f);
+        let stats = |w| p.word(w).unwrap();
+        assert_eq!(stats("f").calls, 2);
+        assert_eq!(stats("g").calls, 1);
+        assert_eq!(stats("+").calls, 1);
+        assert!(p.word("h").is_none());
+        assert!(stats("f").incl >= stats("g").incl + stats("+").incl);
+        assert_eq!(
+            p.folded()
+                .lines()
+                .map(|l| l.rsplit_once(' ').unwrap().0)
+                .collect_vec(),
+            ["f", "f;f", "f;f;+", "f;g"]
+        );
+        assert!(p.report().lines().nth(1).is_some());
+    }
+}
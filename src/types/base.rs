@@ -35,11 +35,15 @@ use crate::{
         EuEnvCtx,
         EuScope,
     },
-    parser::euphrates,
+    parser::{
+        euphrates,
+        input,
+    },
     types::{
         EuRes,
         EuSeq,
         EuSeqT,
+        EuSpan,
         EuSyn,
     },
 };
@@ -62,7 +66,8 @@ pub enum EuType<'eu> {
     #[debug("{_0:?}")]
     Str(LocalHipStr<'eu>),
     #[debug("{_0}")]
-    Word(LocalHipStr<'eu>),
+    #[display("{_0}")]
+    Word(LocalHipStr<'eu>, EuSpan),
 
     #[debug("{}", if let Some(t) = _0 { format!("Some:{t:?}") } else { "None".into() })]
     #[display("{}", if let Some(t) = _0 { t.to_string() } else { String::new() })]
@@ -127,7 +132,7 @@ impl<'eu> EuType<'eu> {
 
     #[inline]
     pub fn word(s: impl Into<LocalHipStr<'eu>>) -> Self {
-        Self::Word(s.into())
+        Self::Word(s.into(), EuSpan::default())
     }
 
     #[inline]
@@ -195,9 +200,9 @@ impl<'eu> EuType<'eu> {
         match self {
             Self::Expr(ts) => Ok(ts),
             Self::Str(s) => euphrates
-                .parse(&s)
+                .parse(input(&s, 0))
                 .map_err(|e| anyhow!(e.into_inner()).into()),
-            _ => Ok(eco_vec![EuSyn::Raw(self)]),
+            _ => Ok(eco_vec![self.into()]),
         }
     }
 
@@ -461,10 +466,10 @@ gen_type_to_num_other!();
 impl<'eu> From<EuSyn<'eu>> for EuType<'eu> {
     fn from(value: EuSyn<'eu>) -> Self {
         match value {
-            EuSyn::Vec(ts) | EuSyn::Map(ts) => Self::Expr(ts),
-            EuSyn::Var(w) | EuSyn::Move(w) | EuSyn::Get(w) => Self::word(w),
-            EuSyn::Raw(t) => t,
-            EuSyn::Bind(ts) => Self::Expr(ts.into_iter().map_into().collect()),
+            EuSyn::Vec(ts, _) | EuSyn::Map(ts, _) => Self::Expr(ts),
+            EuSyn::Var(w, sp) | EuSyn::Move(w, sp) | EuSyn::Get(w, sp) => Self::Word(w, sp),
+            EuSyn::Raw(t, _) => t,
+            EuSyn::Bind(ts, _) => Self::Expr(ts.into_iter().map_into().collect()),
         }
     }
 }
@@ -485,7 +490,7 @@ impl From<&EuType<'_>> for bool {
             EuType::F64(n) => !n.is_zero() && !n.is_nan(),
             EuType::Char(c) => *c != '\0',
             EuType::Str(s) => !s.is_empty(),
-            EuType::Word(_) => true,
+            EuType::Word(..) => true,
             EuType::Opt(o) => o.is_some(),
             EuType::Res(r) => r.is_ok(),
             EuType::Vec(ts) => !ts.is_empty(),
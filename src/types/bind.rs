@@ -11,17 +11,22 @@ use num_traits::Signed;
 use ordered_float::OrderedFloat;
 
 use crate::{
+    env::EuEnvCtx,
     fns::bind,
-    types::EuType,
+    types::{
+        EuSpan,
+        EuType,
+    },
 };
 
 #[derive(Debug, Display, Hash, Clone, IsVariant, PartialEq, Eq, PartialOrd, Ord)]
 pub enum EuBind<'eu> {
     #[debug("{_0}")]
-    Word(LocalHipStr<'eu>),
+    #[display("{_0}")]
+    Word(LocalHipStr<'eu>, EuSpan),
     #[debug("${_0}({})", _1.iter().map(|t| format!("{t:?}")).join(" "))]
     #[display("{}", _1.iter().join(" "))]
-    Tag(LocalHipStr<'eu>, EcoVec<Self>),
+    Tag(LocalHipStr<'eu>, EcoVec<Self>, EuSpan),
     #[debug("({})", _0.iter().map(|t| format!("{t:?}")).join(" "))]
     #[display("{}", _0.iter().join(" "))]
     Union(EcoVec<Self>),
@@ -57,12 +62,12 @@ pub enum EuBind<'eu> {
 impl<'eu> EuBind<'eu> {
     #[inline]
     pub fn word(s: impl Into<LocalHipStr<'eu>>) -> Self {
-        Self::Word(s.into())
+        Self::Word(s.into(), EuSpan::default())
     }
 
     #[inline]
     pub fn tag(w: impl Into<LocalHipStr<'eu>>, bs: impl Into<EcoVec<Self>>) -> Self {
-        Self::Tag(w.into(), bs.into())
+        Self::Tag(w.into(), bs.into(), EuSpan::default())
     }
 
     #[inline]
@@ -111,10 +116,10 @@ impl<'eu> EuBind<'eu> {
     }
 
     #[must_use]
-    pub fn to_free(self) -> Option<EuType<'eu>> {
+    pub fn to_free(self, ctx: &EuEnvCtx) -> Option<EuType<'eu>> {
         match self {
-            EuBind::Bind(b0, b1) => b0.to_free().or_else(|| b1.to_free()),
-            EuBind::Tag(w, bs) => (bind::BIND.get(&w)?.free)(bs),
+            EuBind::Bind(b0, b1) => b0.to_free(ctx).or_else(|| b1.to_free(ctx)),
+            EuBind::Tag(w, bs, _) => (ctx.tag(&w)?.free)(bs, ctx),
             EuBind::Bool(b) => Some(EuType::Bool(b)),
             EuBind::I32(n) => Some(EuType::I32(n)),
             EuBind::I64(n) => Some(EuType::I64(n)),
@@ -122,8 +127,8 @@ impl<'eu> EuBind<'eu> {
             EuBind::F64(n) => Some(EuType::F64(n)),
             EuBind::Char(c) => Some(EuType::Char(c)),
             EuBind::Str(s) => Some(EuType::Str(s)),
-            EuBind::Vecz(bs) => (bind::VECZ.free)(bs),
-            EuBind::Map(bs) => (bind::MAP.free)(bs),
+            EuBind::Vecz(bs) => (bind::VECZ.free)(bs, ctx),
+            EuBind::Map(bs) => (bind::MAP.free)(bs, ctx),
             _ => None,
         }
     }
@@ -0,0 +1,327 @@
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+        HashMap,
+        HashSet,
+    },
+    hash::{
+        BuildHasher,
+        Hash,
+    },
+    rc::Rc,
+};
+
+use anyhow::anyhow;
+use dashu_int::IBig;
+use ecow::EcoVec;
+use hipstr::LocalHipStr;
+use ordered_float::OrderedFloat;
+
+use crate::types::{
+    EuErr,
+    EuRes,
+    EuType,
+};
+
+macro_rules! from_num {
+    ($variant:ident($t:ty): $($n:ty),*) => {
+        $(
+            impl From<$n> for EuType<'_> {
+                fn from(n: $n) -> Self {
+                    Self::$variant(<$t>::from(n))
+                }
+            }
+        )*
+    };
+}
+
+// Integers become the narrowest of `I32` and `I64` that holds every value of
+// their type, or `IBig` if neither does. `isize` and `usize` count as 64 bits,
+// so `isize` is `I64` and `usize` is `IBig`.
+from_num!(I32(i32): i8, i16, i32, u8, u16);
+from_num!(I64(i64): i64, u32);
+from_num!(IBig(IBig): u64, usize, i128, u128, IBig);
+from_num!(F64(OrderedFloat<f64>): f64);
+
+impl From<isize> for EuType<'_> {
+    fn from(n: isize) -> Self {
+        Self::I64(n as i64)
+    }
+}
+
+impl From<f32> for EuType<'_> {
+    fn from(n: f32) -> Self {
+        Self::F64(f64::from(n).into())
+    }
+}
+
+macro_rules! try_from_num {
+    ($($n:ty => $f:ident),*) => {
+        $(
+            impl TryFrom<EuType<'_>> for $n {
+                type Error = EuErr;
+
+                fn try_from(t: EuType<'_>) -> EuRes<Self> {
+                    t.$f()
+                }
+            }
+        )*
+    };
+    ($($n:ty),*) => {
+        $(
+            impl TryFrom<EuType<'_>> for $n {
+                type Error = EuErr;
+
+                fn try_from(t: EuType<'_>) -> EuRes<Self> {
+                    let n = t.try_ibig()?;
+                    n.clone().try_into().map_err(|_| {
+                        anyhow!("{n} does not fit in {}", stringify!($n)).into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+try_from_num!(
+    i32 => try_i32,
+    i64 => try_i64,
+    IBig => try_ibig,
+    f64 => try_f64,
+    u32 => try_u32,
+    u64 => try_u64,
+    usize => try_usize,
+    isize => try_isize
+);
+try_from_num!(i8, i16, i128, u8, u16, u128);
+
+impl TryFrom<EuType<'_>> for f32 {
+    type Error = EuErr;
+
+    #[expect(clippy::cast_possible_truncation, reason = "lossy like `as`")]
+    fn try_from(t: EuType<'_>) -> EuRes<Self> {
+        t.try_f64().map(|n| n as f32)
+    }
+}
+
+impl From<char> for EuType<'_> {
+    fn from(c: char) -> Self {
+        Self::Char(c)
+    }
+}
+
+impl TryFrom<EuType<'_>> for char {
+    type Error = EuErr;
+
+    fn try_from(t: EuType<'_>) -> EuRes<Self> {
+        match t {
+            EuType::Char(c) => Ok(c),
+            EuType::Str(ref s) if s.chars().count() == 1 => {
+                Ok(s.chars().next().unwrap_or_default())
+            }
+            _ => Err(anyhow!("expected char, got `{t:?}`").into()),
+        }
+    }
+}
+
+impl From<&str> for EuType<'_> {
+    fn from(s: &str) -> Self {
+        Self::Str(LocalHipStr::from(s))
+    }
+}
+
+impl From<String> for EuType<'_> {
+    fn from(s: String) -> Self {
+        Self::Str(s.into())
+    }
+}
+
+impl TryFrom<EuType<'_>> for String {
+    type Error = EuErr;
+
+    fn try_from(t: EuType<'_>) -> EuRes<Self> {
+        match t {
+            EuType::Str(s) => Ok(s.into()),
+            EuType::Char(c) => Ok(c.into()),
+            _ => Err(anyhow!("expected string, got `{t:?}`").into()),
+        }
+    }
+}
+
+impl<'eu, T: Into<EuType<'eu>>> From<Option<T>> for EuType<'eu> {
+    fn from(o: Option<T>) -> Self {
+        Self::opt(o.map(Into::into))
+    }
+}
+
+/// Converts `None`, `Err` and `Opt` values as by [`EuType::to_opt`].
+impl<'eu, T> TryFrom<EuType<'eu>> for Option<T>
+where
+    T: TryFrom<EuType<'eu>>,
+    EuErr: From<T::Error>,
+{
+    type Error = EuErr;
+
+    fn try_from(t: EuType<'eu>) -> EuRes<Self> {
+        Ok(t.to_opt().map(T::try_from).transpose()?)
+    }
+}
+
+impl<'eu, T: Into<EuType<'eu>>, E: Into<EuType<'eu>>> From<Result<T, E>> for EuType<'eu> {
+    fn from(r: Result<T, E>) -> Self {
+        Self::res(r.map(Into::into).map_err(Into::into))
+    }
+}
+
+impl<'eu, T, E> TryFrom<EuType<'eu>> for Result<T, E>
+where
+    T: TryFrom<EuType<'eu>>,
+    E: TryFrom<EuType<'eu>>,
+    EuErr: From<T::Error> + From<E::Error>,
+{
+    type Error = EuErr;
+
+    fn try_from(t: EuType<'eu>) -> EuRes<Self> {
+        match t {
+            EuType::Res(Ok(t)) => Ok(Ok(T::try_from(*t)?)),
+            EuType::Res(Err(e)) => Ok(Err(E::try_from(*e)?)),
+            _ => Err(anyhow!("expected Res, got `{t:?}`").into()),
+        }
+    }
+}
+
+impl<'eu, T: Into<EuType<'eu>>> From<Vec<T>> for EuType<'eu> {
+    fn from(ts: Vec<T>) -> Self {
+        Self::Vec(ts.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<'eu, T> TryFrom<EuType<'eu>> for Vec<T>
+where
+    T: TryFrom<EuType<'eu>>,
+    EuErr: From<T::Error>,
+{
+    type Error = EuErr;
+
+    fn try_from(t: EuType<'eu>) -> EuRes<Self> {
+        items(t)?.into_iter().map(|t| Ok(T::try_from(t)?)).collect()
+    }
+}
+
+macro_rules! set {
+    ($set:ty, [$($g:tt)*], $($bound:path),*) => {
+        impl<'eu, T: Into<EuType<'eu>> $($g)*> From<$set> for EuType<'eu> {
+            fn from(ts: $set) -> Self {
+                Self::Set(Rc::new(ts.into_iter().map(Into::into).collect()))
+            }
+        }
+
+        impl<'eu, T $($g)*> TryFrom<EuType<'eu>> for $set
+        where
+            T: TryFrom<EuType<'eu>> $(+ $bound)*,
+            EuErr: From<T::Error>,
+        {
+            type Error = EuErr;
+
+            fn try_from(t: EuType<'eu>) -> EuRes<Self> {
+                items(t)?.into_iter().map(|t| Ok(T::try_from(t)?)).collect()
+            }
+        }
+    };
+}
+
+set!(HashSet<T, S>, [, S: BuildHasher + Default], Eq, Hash);
+set!(BTreeSet<T>, [], Ord);
+
+macro_rules! map {
+    ($map:ty, [$($g:tt)*], $($bound:path),*) => {
+        impl<'eu, K, V $($g)*> From<$map> for EuType<'eu>
+        where
+            K: Into<EuType<'eu>>,
+            V: Into<EuType<'eu>>,
+        {
+            fn from(kvs: $map) -> Self {
+                Self::Map(Rc::new(
+                    kvs.into_iter().map(|(k, v)| (k.into(), v.into())).collect(),
+                ))
+            }
+        }
+
+        impl<'eu, K, V $($g)*> TryFrom<EuType<'eu>> for $map
+        where
+            K: TryFrom<EuType<'eu>> $(+ $bound)*,
+            V: TryFrom<EuType<'eu>>,
+            EuErr: From<K::Error> + From<V::Error>,
+        {
+            type Error = EuErr;
+
+            fn try_from(t: EuType<'eu>) -> EuRes<Self> {
+                if !t.is_many() {
+                    return Err(anyhow!("expected map, got `{t:?}`").into());
+                }
+                Rc::unwrap_or_clone(t.to_map()?)
+                    .into_iter()
+                    .map(|(k, v)| Ok((K::try_from(k)?, V::try_from(v)?)))
+                    .collect()
+            }
+        }
+    };
+}
+
+map!(HashMap<K, V, S>, [, S: BuildHasher + Default], Eq, Hash);
+map!(BTreeMap<K, V>, [], Ord);
+
+/// Items of a `Vec`, `Set`, `Seq` or `Map`, the latter as pairs.
+fn items(t: EuType<'_>) -> EuRes<EcoVec<EuType<'_>>> {
+    if t.is_many() {
+        t.to_vec()
+    } else {
+        Err(anyhow!("expected sequence, got `{t:?}`").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        assert_eq!(EuType::from(3u8), EuType::I32(3));
+        assert_eq!(EuType::from(3u32), EuType::I64(3));
+        assert_eq!(EuType::from(3usize), EuType::IBig(3.into()));
+        assert_eq!(EuType::from(-3isize), EuType::I64(-3));
+        assert_eq!(EuType::from("a"), EuType::str("a"));
+        assert_eq!(
+            EuType::from(vec![Some(1), None]),
+            EuType::vec([EuType::opt(Some(EuType::I32(1))), EuType::Opt(None)])
+        );
+        assert_eq!(
+            EuType::from(BTreeMap::from([('a', Ok::<_, String>(1.5))])),
+            EuType::map_([(EuType::Char('a'), EuType::res(Ok(EuType::F64(1.5.into()))))])
+        );
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(u8::try_from(EuType::I64(255)).unwrap(), 255);
+        assert!(u8::try_from(EuType::I32(256)).is_err());
+        assert!(u8::try_from(EuType::str("a")).is_err());
+        assert_eq!(char::try_from(EuType::str("a")).unwrap(), 'a');
+        assert!(char::try_from(EuType::str("ab")).is_err());
+        assert_eq!(
+            Vec::<Option<i32>>::try_from(EuType::vec([EuType::I32(1), EuType::Opt(None)])).unwrap(),
+            [Some(1), None]
+        );
+        assert!(Vec::<i32>::try_from(EuType::I32(1)).is_err());
+        let m = HashMap::from([("a".to_string(), vec![1u64, 2])]);
+        assert_eq!(
+            HashMap::<String, Vec<u64>>::try_from(EuType::from(m.clone())).unwrap(),
+            m
+        );
+        assert_eq!(
+            Result::<i32, String>::try_from(EuType::res(Err(EuType::str("e")))).unwrap(),
+            Err("e".into())
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fmt::Display,
+    rc::Rc,
+};
+
+use anyhow::anyhow;
+use serde::de::{
+    self,
+    DeserializeOwned,
+    DeserializeSeed,
+    IntoDeserializer,
+    Visitor,
+    value::{
+        MapDeserializer,
+        SeqDeserializer,
+    },
+};
+
+use crate::types::{
+    EuErr,
+    EuRes,
+    EuType,
+};
+
+impl de::Error for EuErr {
+    fn custom<T: Display>(msg: T) -> Self {
+        anyhow!("{msg}").into()
+    }
+}
+
+impl EuType<'_> {
+    /// Deserializes a value into `T`, the inverse of [`EuType::from_serde`].
+    ///
+    /// `Seq`s are forced, `Set`s read as sequences, and `Res` values read as
+    /// the `Ok`/`Err` variants of an enum.
+    pub fn into_serde<T: DeserializeOwned>(self) -> EuRes<T> {
+        T::deserialize(self)
+    }
+}
+
+impl IntoDeserializer<'_, EuErr> for EuType<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for EuType<'_> {
+    type Error = EuErr;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> EuRes<V::Value> {
+        match self {
+            Self::Bool(b) => visitor.visit_bool(b),
+            Self::I32(n) => visitor.visit_i32(n),
+            Self::I64(n) => visitor.visit_i64(n),
+            Self::IBig(n) => {
+                if let Ok(n) = i64::try_from(&n) {
+                    visitor.visit_i64(n)
+                } else if let Ok(n) = u64::try_from(&n) {
+                    visitor.visit_u64(n)
+                } else if let Ok(n) = i128::try_from(&n) {
+                    visitor.visit_i128(n)
+                } else if let Ok(n) = u128::try_from(&n) {
+                    visitor.visit_u128(n)
+                } else {
+                    Err(anyhow!("{n} is too large to deserialize").into())
+                }
+            }
+            Self::F64(n) => visitor.visit_f64(*n),
+            Self::Char(c) => visitor.visit_char(c),
+            Self::Str(s) => visitor.visit_string(s.into()),
+            Self::Opt(None) => visitor.visit_none(),
+            Self::Opt(Some(t)) => visitor.visit_some(*t),
+            Self::Res(r) => visitor.visit_enum(EuEnum::res(r)),
+            Self::Map(kvs) => {
+                let mut de = MapDeserializer::new(Rc::unwrap_or_clone(kvs).into_iter());
+                let v = visitor.visit_map(&mut de)?;
+                de.end()?;
+                Ok(v)
+            }
+            Self::Vec(_) | Self::Set(_) | Self::Seq(_) => {
+                let mut de = SeqDeserializer::new(self.to_vec()?.into_iter());
+                let v = visitor.visit_seq(&mut de)?;
+                de.end()?;
+                Ok(v)
+            }
+            Self::Word(..) | Self::Expr(_) => Err(anyhow!("cannot deserialize `{self:?}`").into()),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> EuRes<V::Value> {
+        match self {
+            Self::Opt(None) => visitor.visit_none(),
+            Self::Opt(Some(t)) => visitor.visit_some(*t),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> EuRes<V::Value> {
+        match self {
+            Self::Opt(None) => visitor.visit_unit(),
+            _ => Err(anyhow!("expected None, got `{self:?}`").into()),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> EuRes<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> EuRes<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> EuRes<V::Value> {
+        match self {
+            Self::Str(_) | Self::Char(_) => visitor.visit_enum(EuEnum {
+                variant: self,
+                value: None,
+            }),
+            Self::Res(r) => visitor.visit_enum(EuEnum::res(r)),
+            Self::Map(kvs) if kvs.len() == 1 => {
+                let (k, v) = Rc::unwrap_or_clone(kvs)
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow!("empty map"))?;
+                visitor.visit_enum(EuEnum {
+                    variant: k,
+                    value: Some(v),
+                })
+            }
+            _ => Err(anyhow!("expected enum variant, got `{self:?}`").into()),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EuEnum<'eu> {
+    variant: EuType<'eu>,
+    value: Option<EuType<'eu>>,
+}
+
+impl<'eu> EuEnum<'eu> {
+    fn res(r: Result<Box<EuType<'eu>>, Box<EuType<'eu>>>) -> Self {
+        let (variant, value) = match r {
+            Ok(t) => ("Ok", t),
+            Err(e) => ("Err", e),
+        };
+        Self {
+            variant: EuType::str(variant),
+            value: Some(*value),
+        }
+    }
+}
+
+impl<'de, 'eu> de::EnumAccess<'de> for EuEnum<'eu> {
+    type Error = EuErr;
+    type Variant = EuVariant<'eu>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> EuRes<(V::Value, Self::Variant)> {
+        Ok((seed.deserialize(self.variant)?, EuVariant(self.value)))
+    }
+}
+
+struct EuVariant<'eu>(Option<EuType<'eu>>);
+
+impl<'de> de::VariantAccess<'de> for EuVariant<'_> {
+    type Error = EuErr;
+
+    fn unit_variant(self) -> EuRes<()> {
+        match self.0 {
+            None | Some(EuType::Opt(None)) => Ok(()),
+            Some(t) => Err(anyhow!("expected unit variant, got `{t:?}`").into()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> EuRes<T::Value> {
+        seed.deserialize(self.0.unwrap_or(EuType::Opt(None)))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> EuRes<V::Value> {
+        de::Deserializer::deserialize_seq(self.0.unwrap_or_default(), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> EuRes<V::Value> {
+        de::Deserializer::deserialize_map(self.0.unwrap_or_default(), visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::{
+        Deserialize,
+        Serialize,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Shape {
+        Dot,
+        Circle(f64),
+        Rect { w: u32, h: u32 },
+        Poly(i8, i8),
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Doc {
+        name: String,
+        tags: Vec<char>,
+        shapes: Vec<Shape>,
+        extra: Option<BTreeMap<String, i128>>,
+        res: Result<u8, String>,
+        unit: (),
+    }
+
+    #[test]
+    fn roundtrip() {
+        let doc = Doc {
+            name: "x".into(),
+            tags: vec!['a', 'b'],
+            shapes: vec![
+                Shape::Dot,
+                Shape::Circle(1.5),
+                Shape::Rect { w: 2, h: 3 },
+                Shape::Poly(-1, 1),
+            ],
+            extra: Some(BTreeMap::from([("big".into(), i128::MAX)])),
+            res: Err("e".into()),
+            unit: (),
+        };
+        let t = EuType::from_serde(&doc).unwrap();
+        assert_eq!(
+            t.clone().to_map().unwrap()[&EuType::str("res")],
+            EuType::map_([(EuType::str("Err"), EuType::str("e"))])
+        );
+        assert_eq!(t.into_serde::<Doc>().unwrap(), doc);
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(
+            EuType::res(Ok(EuType::I32(1)))
+                .into_serde::<Result<u8, String>>()
+                .unwrap(),
+            Ok(1)
+        );
+        assert_eq!(EuType::I32(1).into_serde::<Option<i64>>().unwrap(), Some(1));
+        assert_eq!(
+            EuType::set([EuType::I32(1)])
+                .into_serde::<Vec<u8>>()
+                .unwrap(),
+            [1]
+        );
+        assert!(EuType::str("a").into_serde::<u8>().is_err());
+        assert!(EuType::word("a").into_serde::<String>().is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use ecow::EcoVec;
+use hipstr::LocalHipStr;
+use ordermap::OrderSet;
+
+use crate::types::{
+    EuRes,
+    EuType,
+};
+
+/// Dialect of delimiter-separated values, e.g. CSV or TSV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EuDsvOpts {
+    pub sep: char,
+    /// Quote character, or `None` to read and write fields verbatim.
+    pub quote: Option<char>,
+    /// Whether the first row names the fields of the others.
+    pub header: bool,
+}
+
+impl EuDsvOpts {
+    pub const CSV: Self = Self {
+        sep: ',',
+        quote: Some('"'),
+        header: false,
+    };
+
+    pub const TSV: Self = Self {
+        sep: '\t',
+        ..Self::CSV
+    };
+
+    /// Reads options from a map with optional keys `sep`, `quote` and
+    /// `header`, defaulting to CSV.
+    pub fn from_map(t: EuType) -> EuRes<Self> {
+        let kvs = t.to_map()?;
+        let mut opts = Self::CSV;
+        if let Some(t) = kvs.get(&EuType::str("sep")) {
+            opts.sep = Self::one_char(t, "sep")?.ok_or_else(|| anyhow!("`sep` is empty"))?;
+        }
+        if let Some(t) = kvs.get(&EuType::str("quote")) {
+            opts.quote = Self::one_char(t, "quote")?;
+        }
+        if let Some(t) = kvs.get(&EuType::str("header")) {
+            opts.header = t.clone().into();
+        }
+        if opts.quote == Some(opts.sep) {
+            return Err(anyhow!("`sep` and `quote` must differ").into());
+        }
+        Ok(opts)
+    }
+
+    fn one_char(t: &EuType, name: &str) -> EuRes<Option<char>> {
+        let s = t.clone().to_str();
+        let mut cs = s.chars();
+        match (cs.next(), cs.next()) {
+            (c, None) => Ok(c),
+            _ => Err(anyhow!("`{name}` must be a single char").into()),
+        }
+    }
+}
+
+impl EuType<'_> {
+    /// Parses delimiter-separated text into a `Vec` of rows, each a `Vec` of
+    /// `Str`s, or a `Map` from header to field when `opts.header` is set.
+    /// Blank lines are skipped.
+    pub fn from_dsv(s: &str, opts: EuDsvOpts) -> EuRes<Self> {
+        let mut rows = dsv_rows(s, opts)?.into_iter();
+        if !opts.header {
+            return Ok(Self::Vec(rows.map(Self::Vec).collect()));
+        }
+        let Some(header) = rows.next() else {
+            return Ok(Self::vec([]));
+        };
+        rows.enumerate()
+            .map(|(i, row)| {
+                if row.len() > header.len() {
+                    return Err(anyhow!(
+                        "row {} has {} fields, but the header has {}",
+                        i + 1,
+                        row.len(),
+                        header.len()
+                    )
+                    .into());
+                }
+                let mut row = row.into_iter();
+                Ok(Self::Map(Rc::new(
+                    header
+                        .iter()
+                        .map(|k| (k.clone(), row.next().unwrap_or_else(|| Self::str(""))))
+                        .collect(),
+                )))
+            })
+            .collect::<EuRes<_>>()
+            .map(Self::Vec)
+    }
+
+    /// Writes rows of fields as delimiter-separated text. Rows of `Map`s are
+    /// preceded by a header of all their keys in order of appearance.
+    pub fn to_dsv(self, opts: EuDsvOpts) -> EuRes<String> {
+        let rows = self.to_vec()?;
+        let mut s = String::new();
+        if rows.first().is_some_and(Self::is_map) {
+            let maps: Vec<_> = rows.into_iter().map(Self::to_map).collect::<EuRes<_>>()?;
+            let header: OrderSet<_> = maps.iter().flat_map(|kvs| kvs.keys()).cloned().collect();
+            write_dsv_row(&mut s, header.iter().cloned(), opts)?;
+            for kvs in maps {
+                write_dsv_row(
+                    &mut s,
+                    header
+                        .iter()
+                        .map(|k| kvs.get(k).cloned().unwrap_or_default()),
+                    opts,
+                )?;
+            }
+        } else {
+            for row in rows {
+                write_dsv_row(&mut s, row.to_vec()?, opts)?;
+            }
+        }
+        Ok(s)
+    }
+}
+
+fn dsv_rows<'eu>(s: &str, opts: EuDsvOpts) -> EuRes<Vec<EcoVec<EuType<'eu>>>> {
+    let mut rows = vec![];
+    let mut row = EcoVec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut cs = s.chars().peekable();
+    while let Some(c) = cs.next() {
+        match c {
+            _ if Some(c) == opts.quote && field.is_empty() && !quoted => {
+                let start = line;
+                loop {
+                    match cs.next() {
+                        Some(q) if Some(q) == opts.quote => {
+                            if cs.peek() == Some(&q) {
+                                cs.next();
+                                field.push(q);
+                            } else {
+                                break;
+                            }
+                        }
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(anyhow!("unclosed quote on line {start}").into()),
+                    }
+                }
+                quoted = true;
+                if let Some(&c) = cs.peek()
+                    && !matches!(c, '\n' | '\r')
+                    && c != opts.sep
+                {
+                    return Err(
+                        anyhow!("unexpected `{c}` after quoted field on line {line}").into(),
+                    );
+                }
+            }
+            _ if c == opts.sep => {
+                row.push(EuType::str(LocalHipStr::from(field.as_str())));
+                field.clear();
+                quoted = false;
+            }
+            '\r' if cs.peek() == Some(&'\n') => {}
+            '\n' => {
+                if !row.is_empty() || !field.is_empty() || quoted {
+                    row.push(EuType::str(LocalHipStr::from(field.as_str())));
+                    rows.push(row);
+                }
+                row = EcoVec::new();
+                field.clear();
+                quoted = false;
+                line += 1;
+            }
+            c => field.push(c),
+        }
+    }
+    if !row.is_empty() || !field.is_empty() || quoted {
+        row.push(EuType::str(field));
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn write_dsv_row<'eu>(
+    s: &mut String,
+    row: impl IntoIterator<Item = EuType<'eu>>,
+    opts: EuDsvOpts,
+) -> EuRes<()> {
+    for (i, t) in row.into_iter().enumerate() {
+        if i > 0 {
+            s.push(opts.sep);
+        }
+        let f = t.to_str();
+        let special = |c: char| c == opts.sep || matches!(c, '\n' | '\r') || Some(c) == opts.quote;
+        match opts.quote {
+            Some(q) if f.contains(special) => {
+                s.push(q);
+                for c in f.chars() {
+                    if c == q {
+                        s.push(q);
+                    }
+                    s.push(c);
+                }
+                s.push(q);
+            }
+            None if f.contains(special) => {
+                return Err(
+                    anyhow!("field {f:?} contains a separator and cannot be quoted").into(),
+                );
+            }
+            _ => s.push_str(&f),
+        }
+    }
+    s.push('\n');
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strs<'eu>(ss: &[&str]) -> EuType<'eu> {
+        EuType::vec(ss.iter().map(|s| EuType::str(*s)).collect::<EcoVec<_>>())
+    }
+
+    #[test]
+    fn read() {
+        assert_eq!(
+            EuType::from_dsv("a,b\n\n\"x,\"\"y\"\"\",\r\n\"\"", EuDsvOpts::CSV).unwrap(),
+            EuType::vec([strs(&["a", "b"]), strs(&["x,\"y\"", ""]), strs(&[""])])
+        );
+        assert_eq!(
+            EuType::from_dsv(
+                "a;b\n1\n",
+                EuDsvOpts {
+                    sep: ';',
+                    quote: None,
+                    header: true
+                }
+            )
+            .unwrap(),
+            EuType::vec([EuType::map_([
+                (EuType::str("a"), EuType::str("1")),
+                (EuType::str("b"), EuType::str("")),
+            ])])
+        );
+        assert!(EuType::from_dsv("\"a", EuDsvOpts::CSV).is_err());
+        assert!(EuType::from_dsv("\"a\"b", EuDsvOpts::CSV).is_err());
+        assert!(
+            EuType::from_dsv(
+                "a\n1\t2",
+                EuDsvOpts {
+                    header: true,
+                    ..EuDsvOpts::TSV
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn write() {
+        assert_eq!(
+            EuType::vec([
+                strs(&["a", "b,c"]),
+                EuType::vec([EuType::i32(1), EuType::str("\"")])
+            ])
+            .to_dsv(EuDsvOpts::CSV)
+            .unwrap(),
+            "a,\"b,c\"\n1,\"\"\"\"\n"
+        );
+        assert_eq!(
+            EuType::vec([
+                EuType::map_([(EuType::str("a"), EuType::i32(1))]),
+                EuType::map_([(EuType::str("b"), EuType::i32(2))]),
+            ])
+            .to_dsv(EuDsvOpts::TSV)
+            .unwrap(),
+            "a\tb\n1\t\n\t2\n"
+        );
+        assert!(
+            strs(&["a\tb"])
+                .to_dsv(EuDsvOpts {
+                    quote: None,
+                    ..EuDsvOpts::TSV
+                })
+                .is_err()
+        );
+    }
+}
@@ -1,6 +1,10 @@
 use std::{
     cmp::Ordering,
     error,
+    fmt::{
+        self,
+        Write,
+    },
     hash::{
         Hash,
         Hasher,
@@ -9,9 +13,77 @@ use std::{
 };
 
 use derive_more::Display;
+use ecow::EcoVec;
+use itertools::Itertools;
+
+use crate::types::EuLoc;
 
 #[derive(Debug, Display, Clone)]
-pub struct EuErr(pub Arc<anyhow::Error>);
+#[display("{_0}")]
+pub struct EuErr(
+    pub Arc<anyhow::Error>,
+    pub Option<Arc<EuLoc>>,
+    pub EcoVec<EuFrame>,
+);
+
+/// A user word or expression being evaluated when an error was raised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EuFrame {
+    pub name: String,
+    pub loc: Option<EuLoc>,
+}
+
+impl EuErr {
+    /// Attaches `loc` unless a more specific location is already known.
+    #[must_use]
+    pub fn at(self, loc: impl FnOnce() -> Option<EuLoc>) -> Self {
+        match self {
+            Self(e, None, fs) => Self(e, loc().map(Arc::new), fs),
+            _ => self,
+        }
+    }
+
+    /// Attaches the call stack unless one was captured closer to the error.
+    #[must_use]
+    pub fn traced(self, fs: impl FnOnce() -> EcoVec<EuFrame>) -> Self {
+        match self {
+            Self(e, loc, t) if t.is_empty() => Self(e, loc, fs()),
+            _ => self,
+        }
+    }
+
+    #[must_use]
+    pub fn report(&self) -> String {
+        let mut s = String::from("ERR:\n");
+        for c in self.0.chain() {
+            _ = writeln!(s, "{c}");
+        }
+        if let Some(loc) = &self.1 {
+            s.push_str(&loc.snippet());
+        }
+        if !self.2.is_empty() {
+            s.push_str("backtrace:\n");
+            for (i, (n, f)) in self.2.iter().dedup_with_count().enumerate() {
+                _ = write!(s, "{i:>4}: {f}");
+                if n > 1 {
+                    _ = write!(s, " ({n} times)");
+                }
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl fmt::Display for EuFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`", self.name)?;
+        if let Some(loc) = &self.loc {
+            write!(f, " at {loc}")?;
+        }
+        Ok(())
+    }
+}
 
 impl error::Error for EuErr {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
@@ -21,7 +93,11 @@ impl error::Error for EuErr {
 
 impl From<anyhow::Error> for EuErr {
     fn from(err: anyhow::Error) -> Self {
-        Self(Arc::new(err))
+        let (loc, fs) = err
+            .downcast_ref::<Self>()
+            .map(|e| (e.1.clone(), e.2.clone()))
+            .unwrap_or_default();
+        Self(Arc::new(err), loc, fs)
     }
 }
 
@@ -0,0 +1,288 @@
+use std::{
+    fmt::Write,
+    iter,
+};
+
+use anyhow::{
+    Context,
+    anyhow,
+};
+use dashu_int::Sign;
+use ordermap::OrderMap;
+
+use crate::types::{
+    EuRes,
+    EuType,
+    str::try_string,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EuFmtPiece<'i> {
+    Lit(&'i str),
+    Arg(EuFmtArg<'i>, EuFmtSpec),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EuFmtArg<'i> {
+    Next,
+    Index(usize),
+    Name(&'i str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EuFmtSpec {
+    pub fill: char,
+    pub align: Option<EuFmtAlign>,
+    pub sign: bool,
+    pub alt: bool,
+    pub zero: bool,
+    pub width: Option<usize>,
+    pub prec: Option<usize>,
+    pub ty: EuFmtTy,
+}
+
+impl Default for EuFmtSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: None,
+            sign: false,
+            alt: false,
+            zero: false,
+            width: None,
+            prec: None,
+            ty: EuFmtTy::Display,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EuFmtAlign {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EuFmtTy {
+    Display,
+    Debug,
+    Bin,
+    Oct,
+    LowerHex,
+    UpperHex,
+    LowerExp,
+    UpperExp,
+}
+
+impl EuFmtPiece<'_> {
+    /// Sum of the widths arguments are padded to, which bounds the padding
+    /// formatting adds.
+    #[must_use]
+    pub fn width(ps: &[Self]) -> usize {
+        ps.iter()
+            .map(|p| match p {
+                Self::Arg(_, spec) => spec.width.unwrap_or(0),
+                Self::Lit(_) => 0,
+            })
+            .fold(0, usize::saturating_add)
+    }
+
+    #[must_use]
+    pub fn arity(ps: &[Self]) -> (usize, bool) {
+        let mut next = 0;
+        let mut n = 0;
+        let mut named = false;
+        for p in ps {
+            match p {
+                Self::Arg(EuFmtArg::Next, _) => next += 1,
+                Self::Arg(EuFmtArg::Index(i), _) => n = n.max(i + 1),
+                Self::Arg(EuFmtArg::Name(_), _) => named = true,
+                Self::Lit(_) => {}
+            }
+        }
+        (n.max(next), named)
+    }
+}
+
+impl EuType<'_> {
+    pub fn format(
+        ps: &[EuFmtPiece],
+        args: &[Self],
+        kvs: Option<&OrderMap<Self, Self>>,
+    ) -> EuRes<Self> {
+        let mut next = 0;
+        let mut res = String::new();
+        for p in ps {
+            match *p {
+                EuFmtPiece::Lit(s) => res.push_str(s),
+                EuFmtPiece::Arg(a, spec) => {
+                    let t = match a {
+                        EuFmtArg::Next => {
+                            next += 1;
+                            args.get(next - 1)
+                                .with_context(|| format!("missing arg {}", next - 1))?
+                        }
+                        EuFmtArg::Index(i) => {
+                            args.get(i).with_context(|| format!("missing arg {i}"))?
+                        }
+                        EuFmtArg::Name(w) => kvs
+                            .and_then(|kvs| kvs.get(&Self::str(w.to_owned())))
+                            .with_context(|| format!("missing key `{w}`"))?,
+                    };
+                    let s = t.fmt_spec(&spec)?;
+                    res.try_reserve(s.len()).map_err(|e| anyhow!(e))?;
+                    res.push_str(&s);
+                }
+            }
+        }
+        Ok(Self::str(res))
+    }
+
+    pub fn fmt_spec(&self, spec: &EuFmtSpec) -> EuRes<String> {
+        let (neg, prefix, body) = match spec.ty {
+            EuFmtTy::Display => match (self, spec.prec) {
+                (Self::F64(n), Some(p)) => split_sign(format!("{:.p$}", n.0)),
+                (Self::Str(s), Some(p)) => (false, "", s.chars().take(p).collect()),
+                (t, _) if t.is_num() => split_sign(t.to_string()),
+                (t, _) => (false, "", t.to_string()),
+            },
+            EuFmtTy::Debug => (false, "", format!("{self:?}")),
+            EuFmtTy::Bin | EuFmtTy::Oct | EuFmtTy::LowerHex | EuFmtTy::UpperHex => {
+                let (sign, n) = self.try_ibig()?.into_parts();
+                let (prefix, body) = match spec.ty {
+                    EuFmtTy::Bin => ("0b", format!("{n:b}")),
+                    EuFmtTy::Oct => ("0o", format!("{n:o}")),
+                    EuFmtTy::LowerHex => ("0x", format!("{n:x}")),
+                    _ => ("0x", format!("{n:X}")),
+                };
+                (
+                    sign == Sign::Negative,
+                    if spec.alt { prefix } else { "" },
+                    body,
+                )
+            }
+            EuFmtTy::LowerExp | EuFmtTy::UpperExp => {
+                let n = self.try_f64()?;
+                let mut s = String::new();
+                match (spec.ty, spec.prec) {
+                    (EuFmtTy::LowerExp, Some(p)) => write!(s, "{n:.p$e}"),
+                    (EuFmtTy::LowerExp, None) => write!(s, "{n:e}"),
+                    (_, Some(p)) => write!(s, "{n:.p$E}"),
+                    (_, None) => write!(s, "{n:E}"),
+                }
+                .map_err(|e| anyhow!(e))?;
+                split_sign(s)
+            }
+        };
+
+        let sign = if neg {
+            "-"
+        } else if spec.sign && (self.is_num() || spec.ty != EuFmtTy::Display) {
+            "+"
+        } else {
+            ""
+        };
+        let len = sign.len() + prefix.len() + body.chars().count();
+        let width = spec.width.unwrap_or(0);
+
+        let pad = width.saturating_sub(len);
+        let (l, zeros, r) = if spec.zero && spec.align.is_none() {
+            (0, pad, 0)
+        } else {
+            let align = spec.align.unwrap_or(if self.is_num() {
+                EuFmtAlign::Right
+            } else {
+                EuFmtAlign::Left
+            });
+            match align {
+                EuFmtAlign::Left => (0, 0, pad),
+                EuFmtAlign::Center => (pad / 2, 0, pad - pad / 2),
+                EuFmtAlign::Right => (pad, 0, 0),
+            }
+        };
+        let mut s = try_string(
+            (l + r)
+                .checked_mul(spec.fill.len_utf8())
+                .and_then(|n| n.checked_add(zeros))
+                .and_then(|n| n.checked_add(sign.len() + prefix.len() + body.len())),
+        )?;
+        s.extend(iter::repeat_n(spec.fill, l));
+        s.push_str(sign);
+        s.push_str(prefix);
+        s.extend(iter::repeat_n('0', zeros));
+        s.push_str(&body);
+        s.extend(iter::repeat_n(spec.fill, r));
+        Ok(s)
+    }
+}
+
+fn split_sign(s: String) -> (bool, &'static str, String) {
+    match s.strip_prefix('-') {
+        Some(t) => (true, "", t.into()),
+        None => (false, "", s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winnow::Parser;
+
+    use super::*;
+    use crate::parser::fmt_template;
+
+    fn format<'eu>(
+        template: &str,
+        args: &[EuType<'eu>],
+        kvs: Option<&OrderMap<EuType<'eu>, EuType<'eu>>>,
+    ) -> EuRes<EuType<'eu>> {
+        EuType::format(&fmt_template.parse(template).unwrap(), args, kvs)
+    }
+
+    fn fmt(template: &str, args: &[EuType<'static>]) -> String {
+        format(template, args, None).unwrap().to_string()
+    }
+
+    #[test]
+    fn positional() {
+        let args = [EuType::ibig(1), EuType::str("a")];
+        assert_eq!(fmt("{} {}", &args), "1 a");
+        assert_eq!(fmt("{1}{0}{1}", &args), "a1a");
+        assert_eq!(fmt("{{{}}}", &args), "{1}");
+        assert!(format("{2}", &args, None).is_err());
+    }
+
+    #[test]
+    fn named() {
+        let kvs = OrderMap::from([(EuType::str("x"), EuType::f64(1.5))]);
+        assert_eq!(
+            format("x={x:.2}", &[], Some(&kvs)).unwrap(),
+            EuType::str("x=1.50")
+        );
+        assert!(format("{y}", &[], Some(&kvs)).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(fmt_template.parse("{").is_err());
+        assert!(fmt_template.parse("}").is_err());
+        assert!(fmt_template.parse("{:.}").is_err());
+    }
+
+    #[test]
+    fn spec() {
+        assert_eq!(fmt("{:>5}", &[EuType::str("ab")]), "   ab");
+        assert_eq!(fmt("{:*^6}", &[EuType::str("ab")]), "**ab**");
+        assert_eq!(fmt("{:5}", &[EuType::i32(-3)]), "   -3");
+        assert_eq!(fmt("{:+05}", &[EuType::i32(3)]), "+0003");
+        assert_eq!(fmt("{:#x}", &[EuType::i64(-255)]), "-0xff");
+        assert_eq!(fmt("{:08b}", &[EuType::ibig(5)]), "00000101");
+        assert_eq!(fmt("{:.2e}", &[EuType::f64(1234.5)]), "1.23e3");
+        assert_eq!(fmt("{:?}", &[EuType::str("a")]), r#""a""#);
+        assert_eq!(fmt("{:.1}", &[EuType::str("abc")]), "a");
+        for width in [usize::MAX, 1 << 62] {
+            assert!(format(&format!("{{:{width}}}"), &[EuType::i32(1)], None).is_err());
+            assert!(format(&format!("{{:0{width}}}"), &[EuType::i32(1)], None).is_err());
+        }
+    }
+}
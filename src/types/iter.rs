@@ -62,7 +62,8 @@ impl<'eu> EuType<'eu> {
             let f = f.to_expr()?;
             Ok(Self::seq(self.unfold(move |acc| {
                 EuEnv::apply_n_2(f.clone(), slice::from_mut(acc), scope.clone(), ctx)
-            })))
+            }))
+            .metered(ctx))
         })
     }
 
@@ -0,0 +1,149 @@
+use std::fmt::Write;
+
+use anyhow::anyhow;
+use winnow::Parser;
+
+use crate::{
+    parser::json,
+    types::{
+        EuRes,
+        EuType,
+    },
+};
+
+impl EuType<'_> {
+    /// Encodes a value as compact JSON. `Opt`s are encoded as their value or
+    /// `null`, and non-string map keys are converted to strings.
+    pub fn to_json(&self) -> EuRes<String> {
+        let mut s = String::new();
+        self.write_json(&mut s)?;
+        Ok(s)
+    }
+
+    fn write_json(&self, s: &mut String) -> EuRes<()> {
+        match self {
+            Self::Bool(b) => _ = write!(s, "{b}"),
+            Self::I32(n) => _ = write!(s, "{n}"),
+            Self::I64(n) => _ = write!(s, "{n}"),
+            Self::IBig(n) => _ = write!(s, "{n}"),
+            Self::F64(n) if n.is_finite() => _ = write!(s, "{:?}", n.0),
+            Self::Char(c) => write_json_str(s, c.encode_utf8(&mut [0; 4])),
+            Self::Str(t) => write_json_str(s, t),
+            Self::Opt(None) => s.push_str("null"),
+            Self::Opt(Some(t)) | Self::Res(Ok(t)) => t.write_json(s)?,
+            Self::Vec(ts) => write_json_arr(s, ts.iter().cloned().map(Ok))?,
+            Self::Set(ts) => write_json_arr(s, ts.iter().cloned().map(Ok))?,
+            Self::Seq(it) => write_json_arr(s, it.clone())?,
+            Self::Map(kvs) => {
+                s.push('{');
+                for (i, (k, v)) in kvs.iter().enumerate() {
+                    if i > 0 {
+                        s.push(',');
+                    }
+                    match k {
+                        Self::Str(k) => write_json_str(s, k),
+                        k => write_json_str(s, &k.to_string()),
+                    }
+                    s.push(':');
+                    v.write_json(s)?;
+                }
+                s.push('}');
+            }
+            _ => return Err(anyhow!("cannot encode {self:?} as JSON").into()),
+        }
+        Ok(())
+    }
+
+    pub fn from_json(s: &str) -> EuRes<Self> {
+        json.parse(s)
+            .map_err(|e| anyhow!("invalid JSON at byte {}: {}", e.offset(), e.inner()).into())
+    }
+}
+
+fn write_json_arr<'eu>(s: &mut String, ts: impl Iterator<Item = EuRes<EuType<'eu>>>) -> EuRes<()> {
+    s.push('[');
+    for (i, t) in ts.enumerate() {
+        if i > 0 {
+            s.push(',');
+        }
+        t?.write_json(s)?;
+    }
+    s.push(']');
+    Ok(())
+}
+
+fn write_json_str(s: &mut String, t: &str) {
+    s.push('"');
+    for c in t.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c < ' ' => _ = write!(s, "\\u{:04x}", c as u32),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let t = EuType::from_json(
+            r#" {"a": [1, -2.5e1, true, null], "b": {"c": "x\"\u00e9\ud83d\ude00"},
+                "big": 123456789012345678901234567890, "a": false} "#,
+        )
+        .unwrap();
+        assert_eq!(
+            t,
+            EuType::map_([
+                (EuType::str("a"), EuType::Bool(false)),
+                (
+                    EuType::str("b"),
+                    EuType::map_([(EuType::str("c"), EuType::str("x\"é😀"))])
+                ),
+                (
+                    EuType::str("big"),
+                    EuType::ibig(
+                        "123456789012345678901234567890"
+                            .parse::<dashu_int::IBig>()
+                            .unwrap()
+                    )
+                ),
+            ])
+        );
+        assert_eq!(EuType::from_json("[]").unwrap(), EuType::vec([]));
+        assert_eq!(EuType::from_json("-7").unwrap(), EuType::i64(-7));
+        for s in [
+            "",
+            "[1,]",
+            "{\"a\" 1}",
+            "01",
+            "\"\\x\"",
+            "\"\\ud800\"",
+            "[1] 2",
+        ] {
+            assert!(EuType::from_json(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn encode() {
+        let t = EuType::vec([
+            EuType::i32(1),
+            EuType::f64(2.0),
+            EuType::str("a\"\n\u{1}"),
+            EuType::opt(None),
+            EuType::map_([(EuType::i32(3), EuType::set([EuType::char('c')]))]),
+        ]);
+        let s = t.to_json().unwrap();
+        assert_eq!(s, r#"[1,2.0,"a\"\n\u0001",null,{"3":["c"]}]"#);
+        assert!(EuType::f64(f64::NAN).to_json().is_err());
+        assert!(EuType::word("x").to_json().is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{
+    env::EuEnvCtx,
+    types::EuType,
+};
+
+impl<'eu> EuType<'eu> {
+    /// Number of elements directly held by a collection or string, and 0 for
+    /// anything else.
+    #[must_use]
+    pub fn elems(&self) -> usize {
+        match self {
+            Self::Str(s) => s.chars().count(),
+            Self::Vec(ts) => ts.len(),
+            Self::Map(kvs) => kvs.len(),
+            Self::Set(ts) => ts.len(),
+            _ => 0,
+        }
+    }
+
+    /// Makes each item pulled from a `Seq` spend a step of `ctx`'s fuel and an
+    /// element of its budget, so that consuming an infinite `Seq` cannot
+    /// outrun the limits.
+    #[must_use]
+    pub fn metered(self, ctx: &'eu EuEnvCtx) -> Self {
+        let opts = &ctx.opts;
+        match self {
+            Self::Seq(it)
+                if opts.fuel.is_some() || opts.timeout.is_some() || opts.elem_budget.is_some() =>
+            {
+                Self::seq(it.map(move |t| ctx.burn().and_then(|()| ctx.charge_elems(1)).and(t)))
+            }
+            _ => self,
+        }
+    }
+}
@@ -1,10 +1,23 @@
 mod base;
 mod bind;
+mod conv;
+#[cfg(feature = "serde")]
+mod de;
+mod dsv;
 mod err;
+mod fmt;
 mod into_iter;
 mod iter;
+mod json;
+mod limit;
 mod num;
 mod ord;
+mod owned;
+#[cfg(feature = "serde")]
+mod ser;
+mod source;
+mod span;
+mod str;
 mod syn;
 mod vecz;
 
@@ -15,8 +28,11 @@ pub use std::hash::{
 
 pub use base::*;
 pub use bind::*;
+pub use dsv::*;
 use dyn_clone::DynClone;
 pub use err::*;
+pub use fmt::*;
+pub use span::*;
 pub use syn::*;
 
 pub type EuIter<'eu, T = EuSyn<'eu>> = Box<dyn Iterator<Item = T> + 'eu>;
@@ -16,7 +16,7 @@ impl EuType<'_> {
             Self::I64(_) => 3,
             Self::IBig(_) => 4,
             Self::F64(_) => 5,
-            Self::Word(_) => 6,
+            Self::Word(..) => 6,
             Self::Str(_) => 7,
             Self::Opt(_) => 8,
             Self::Res(_) => 9,
@@ -56,7 +56,7 @@ fn gen_partial_eq() {
     let arms = types
         .map(|t| {
             crabtime::quote! {
-                (Self::{{t}}(l0), Self::{{t}}(r0)) => l0 == r0,
+                (Self::{{t}}(l0, ..), Self::{{t}}(r0, ..)) => l0 == r0,
             }
         })
         .join("");
@@ -93,7 +93,7 @@ fn gen_ord() {
     let arms = types
         .map(|t| {
             crabtime::quote! {
-                (Self::{{t}}(l0), Self::{{t}}(r0)) => l0.cmp(r0),
+                (Self::{{t}}(l0, ..), Self::{{t}}(r0, ..)) => l0.cmp(r0),
             }
         })
         .join("");
@@ -106,8 +106,8 @@ fn gen_ord() {
                     (Self::Seq(l0), Self::Seq(r0)) => l0.clone().cmp(r0.clone()),
                     (Self::Bool(l0), _) => l0.cmp(&!l0),
                     (_, Self::Bool(r0)) => r0.cmp(&!r0).reverse(),
-                    (Self::Word(_), _) => Ordering::Greater,
-                    (l0, r0 @ Self::Word(_)) => r0.cmp(l0).reverse(),
+                    (Self::Word(..), _) => Ordering::Greater,
+                    (l0, r0 @ Self::Word(..)) => r0.cmp(l0).reverse(),
                     (a, b) if a.is_int() && b.is_int() => {
                         a.to_ibig().unwrap().cmp(&b.to_ibig().unwrap()).then_with(|| a.eqv_ord(b))
                     }
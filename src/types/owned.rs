@@ -0,0 +1,94 @@
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use itertools::Itertools;
+
+use crate::types::{
+    EuBind,
+    EuErr,
+    EuRes,
+    EuSyn,
+    EuType,
+};
+
+impl EuType<'_> {
+    /// Copies any borrowed strings, so the value can outlive the evaluation
+    /// that produced it. Fails on [`Self::Seq`], whose iterator may borrow
+    /// the context it was built in.
+    pub fn into_owned<'a>(self) -> EuRes<EuType<'a>> {
+        Ok(match self {
+            Self::Bool(b) => EuType::Bool(b),
+            Self::I32(n) => EuType::I32(n),
+            Self::I64(n) => EuType::I64(n),
+            Self::IBig(n) => EuType::IBig(n),
+            Self::F64(n) => EuType::F64(n),
+            Self::Char(c) => EuType::Char(c),
+            Self::Str(s) => EuType::Str(s.into_owned()),
+            Self::Word(w, sp) => EuType::Word(w.into_owned(), sp),
+            Self::Opt(o) => EuType::Opt(o.map(|t| t.into_owned().map(Box::new)).transpose()?),
+            Self::Res(Ok(t)) => EuType::Res(Ok(Box::new(t.into_owned()?))),
+            Self::Res(Err(e)) => EuType::Res(Err(Box::new(e.into_owned()?))),
+            Self::Vec(ts) => EuType::Vec(ts.into_iter().map(Self::into_owned).try_collect()?),
+            Self::Map(kvs) => EuType::Map(Rc::new(
+                Rc::unwrap_or_clone(kvs)
+                    .into_iter()
+                    .map(|(k, v)| Ok::<_, EuErr>((k.into_owned()?, v.into_owned()?)))
+                    .try_collect()?,
+            )),
+            Self::Set(ts) => EuType::Set(Rc::new(
+                Rc::unwrap_or_clone(ts)
+                    .into_iter()
+                    .map(Self::into_owned)
+                    .try_collect()?,
+            )),
+            Self::Expr(ss) => EuType::Expr(ss.into_iter().map(EuSyn::into_owned).try_collect()?),
+            Self::Seq(_) => return Err(anyhow!("`Seq` cannot outlive its evaluation").into()),
+        })
+    }
+}
+
+impl EuSyn<'_> {
+    /// See [`EuType::into_owned`].
+    pub fn into_owned<'a>(self) -> EuRes<EuSyn<'a>> {
+        Ok(match self {
+            Self::Raw(t, sp) => EuSyn::Raw(t.into_owned()?, sp),
+            Self::Var(w, sp) => EuSyn::Var(w.into_owned(), sp),
+            Self::Move(w, sp) => EuSyn::Move(w.into_owned(), sp),
+            Self::Get(w, sp) => EuSyn::Get(w.into_owned(), sp),
+            Self::Vec(ss, sp) => {
+                EuSyn::Vec(ss.into_iter().map(Self::into_owned).try_collect()?, sp)
+            }
+            Self::Map(ss, sp) => {
+                EuSyn::Map(ss.into_iter().map(Self::into_owned).try_collect()?, sp)
+            }
+            Self::Bind(bs, sp) => EuSyn::Bind(bs.into_iter().map(EuBind::into_owned).collect(), sp),
+        })
+    }
+}
+
+impl EuBind<'_> {
+    /// See [`EuType::into_owned`]. Patterns hold no sequences, so this
+    /// cannot fail.
+    #[must_use]
+    pub fn into_owned<'a>(self) -> EuBind<'a> {
+        match self {
+            Self::Word(w, sp) => EuBind::Word(w.into_owned(), sp),
+            Self::Tag(w, bs, sp) => EuBind::Tag(
+                w.into_owned(),
+                bs.into_iter().map(Self::into_owned).collect(),
+                sp,
+            ),
+            Self::Union(bs) => EuBind::Union(bs.into_iter().map(Self::into_owned).collect()),
+            Self::Bind(a, b) => EuBind::Bind(Box::new(a.into_owned()), Box::new(b.into_owned())),
+            Self::Bool(b) => EuBind::Bool(b),
+            Self::I32(n) => EuBind::I32(n),
+            Self::I64(n) => EuBind::I64(n),
+            Self::IBig(n) => EuBind::IBig(n),
+            Self::F64(n) => EuBind::F64(n),
+            Self::Char(c) => EuBind::Char(c),
+            Self::Str(s) => EuBind::Str(s.into_owned()),
+            Self::Vecz(bs) => EuBind::Vecz(bs.into_iter().map(Self::into_owned).collect()),
+            Self::Map(bs) => EuBind::Map(bs.into_iter().map(Self::into_owned).collect()),
+        }
+    }
+}
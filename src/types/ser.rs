@@ -0,0 +1,336 @@
+use std::{
+    fmt::Display,
+    marker::PhantomData,
+};
+
+use anyhow::anyhow;
+use ecow::EcoVec;
+use ordermap::OrderMap;
+use serde::ser::{
+    self,
+    Serialize,
+};
+
+use crate::types::{
+    EuErr,
+    EuRes,
+    EuType,
+};
+
+impl ser::Error for EuErr {
+    fn custom<T: Display>(msg: T) -> Self {
+        anyhow!("{msg}").into()
+    }
+}
+
+impl EuType<'_> {
+    /// Serializes `t` into a value.
+    ///
+    /// Structs and maps become `Map`s, sequences and tuples become `Vec`s,
+    /// unit values become `None`, and enum variants become either their name
+    /// or a single-entry `Map` from name to contents.
+    pub fn from_serde<T: Serialize + ?Sized>(t: &T) -> EuRes<Self> {
+        t.serialize(EuSerializer(PhantomData))
+    }
+}
+
+struct EuSerializer<'eu>(PhantomData<EuType<'eu>>);
+
+impl<'eu> EuSerializer<'eu> {
+    fn variant(name: &'static str, t: EuType<'eu>) -> EuType<'eu> {
+        EuType::map_(OrderMap::from([(EuType::str(name), t)]))
+    }
+}
+
+impl<'eu> ser::Serializer for EuSerializer<'eu> {
+    type Ok = EuType<'eu>;
+    type Error = EuErr;
+    type SerializeSeq = EuSerVec<'eu>;
+    type SerializeTuple = EuSerVec<'eu>;
+    type SerializeTupleStruct = EuSerVec<'eu>;
+    type SerializeTupleVariant = EuSerVec<'eu>;
+    type SerializeMap = EuSerMap<'eu>;
+    type SerializeStruct = EuSerMap<'eu>;
+    type SerializeStructVariant = EuSerMap<'eu>;
+
+    fn serialize_bool(self, v: bool) -> EuRes<Self::Ok> {
+        Ok(EuType::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_i128(self, v: i128) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_u8(self, v: u8) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_u128(self, v: u128) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_f32(self, v: f32) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_char(self, v: char) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_str(self, v: &str) -> EuRes<Self::Ok> {
+        Ok(v.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> EuRes<Self::Ok> {
+        Ok(EuType::Vec(v.iter().map(|&b| b.into()).collect()))
+    }
+
+    fn serialize_none(self) -> EuRes<Self::Ok> {
+        Ok(EuType::Opt(None))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> EuRes<Self::Ok> {
+        Ok(EuType::opt(Some(value.serialize(self)?)))
+    }
+
+    fn serialize_unit(self) -> EuRes<Self::Ok> {
+        Ok(EuType::Opt(None))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> EuRes<Self::Ok> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> EuRes<Self::Ok> {
+        Ok(EuType::str(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> EuRes<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> EuRes<Self::Ok> {
+        Ok(Self::variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> EuRes<Self::SerializeSeq> {
+        Ok(EuSerVec {
+            ts: EcoVec::with_capacity(len.unwrap_or_default()),
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> EuRes<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> EuRes<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> EuRes<Self::SerializeTupleVariant> {
+        Ok(EuSerVec {
+            ts: EcoVec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> EuRes<Self::SerializeMap> {
+        Ok(EuSerMap {
+            kvs: OrderMap::with_capacity(len.unwrap_or_default()),
+            key: None,
+            variant: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> EuRes<Self::SerializeStruct> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> EuRes<Self::SerializeStructVariant> {
+        Ok(EuSerMap {
+            kvs: OrderMap::with_capacity(len),
+            key: None,
+            variant: Some(variant),
+        })
+    }
+}
+
+struct EuSerVec<'eu> {
+    ts: EcoVec<EuType<'eu>>,
+    variant: Option<&'static str>,
+}
+
+impl<'eu> EuSerVec<'eu> {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> EuRes<()> {
+        self.ts.push(EuType::from_serde(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> EuType<'eu> {
+        let t = EuType::Vec(self.ts);
+        match self.variant {
+            Some(v) => EuSerializer::variant(v, t),
+            None => t,
+        }
+    }
+}
+
+macro_rules! ser_vec {
+    ($($tr:ident::$f:ident),*) => {
+        $(
+            impl<'eu> ser::$tr for EuSerVec<'eu> {
+                type Ok = EuType<'eu>;
+                type Error = EuErr;
+
+                fn $f<T: Serialize + ?Sized>(&mut self, value: &T) -> EuRes<()> {
+                    self.push(value)
+                }
+
+                fn end(self) -> EuRes<Self::Ok> {
+                    Ok(self.finish())
+                }
+            }
+        )*
+    };
+}
+
+ser_vec!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+struct EuSerMap<'eu> {
+    kvs: OrderMap<EuType<'eu>, EuType<'eu>>,
+    key: Option<EuType<'eu>>,
+    variant: Option<&'static str>,
+}
+
+impl<'eu> EuSerMap<'eu> {
+    fn field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> EuRes<()> {
+        self.kvs
+            .insert(EuType::str(key), EuType::from_serde(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> EuType<'eu> {
+        let t = EuType::map_(self.kvs);
+        match self.variant {
+            Some(v) => EuSerializer::variant(v, t),
+            None => t,
+        }
+    }
+}
+
+impl<'eu> ser::SerializeMap for EuSerMap<'eu> {
+    type Ok = EuType<'eu>;
+    type Error = EuErr;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> EuRes<()> {
+        self.key = Some(EuType::from_serde(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> EuRes<()> {
+        let k = self
+            .key
+            .take()
+            .ok_or_else(|| anyhow!("map value without key"))?;
+        self.kvs.insert(k, EuType::from_serde(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> EuRes<Self::Ok> {
+        Ok(self.finish())
+    }
+}
+
+macro_rules! ser_struct {
+    ($($tr:ident),*) => {
+        $(
+            impl<'eu> ser::$tr for EuSerMap<'eu> {
+                type Ok = EuType<'eu>;
+                type Error = EuErr;
+
+                fn serialize_field<T: Serialize + ?Sized>(
+                    &mut self,
+                    key: &'static str,
+                    value: &T,
+                ) -> EuRes<()> {
+                    self.field(key, value)
+                }
+
+                fn end(self) -> EuRes<Self::Ok> {
+                    Ok(self.finish())
+                }
+            }
+        )*
+    };
+}
+
+ser_struct!(SerializeStruct, SerializeStructVariant);
@@ -0,0 +1,211 @@
+use std::rc::Rc;
+
+use anyhow::{
+    Context,
+    anyhow,
+};
+use dashu_int::ops::UnsignedAbs;
+use ecow::EcoVec;
+use itertools::Itertools;
+use num_traits::Signed;
+use ordered_float::{
+    FloatCore,
+    OrderedFloat,
+};
+use winnow::Parser;
+
+use crate::{
+    parser::{
+        euphrates,
+        input,
+    },
+    types::{
+        EuBind,
+        EuRes,
+        EuSyn,
+        EuType,
+    },
+};
+
+impl EuSyn<'_> {
+    /// Renders a program as normalized source that parses back to `ts`.
+    #[must_use]
+    pub fn fmt_source(ts: &[Self]) -> String {
+        ts.iter().map(Self::to_source).join(" ")
+    }
+
+    #[must_use]
+    pub fn to_source(&self) -> String {
+        match self {
+            Self::Raw(t, _) => t.to_source(),
+            Self::Var(w, _) => format!("${w}"),
+            Self::Move(w, _) => format!("\\{w}"),
+            Self::Get(w, _) => format!(".{w}"),
+            Self::Vec(ts, _) => format!("[{}]", Self::fmt_source(ts)),
+            Self::Map(ts, _) => format!("{{{}}}", Self::fmt_source(ts)),
+            Self::Bind(bs, _) => format!("\\[{}]", EuBind::fmt_source(bs)),
+        }
+    }
+}
+
+impl EuBind<'_> {
+    #[must_use]
+    pub fn fmt_source(bs: &[Self]) -> String {
+        bs.iter().map(Self::to_source).join(" ")
+    }
+
+    #[must_use]
+    pub fn to_source(&self) -> String {
+        match self {
+            Self::Word(w, _) => w.to_string(),
+            Self::Tag(w, bs, _) => format!("${w}({})", Self::fmt_source(bs)),
+            Self::Union(bs) => format!("({})", Self::fmt_source(bs)),
+            Self::Bind(b0, b1) => format!("{}\\{}", b0.to_source(), b1.to_source()),
+            Self::Bool(b) => EuType::Bool(*b).to_source(),
+            Self::I32(n) => EuType::I32(*n).to_source(),
+            Self::I64(n) => EuType::I64(*n).to_source(),
+            Self::IBig(n) => EuType::IBig(n.clone()).to_source(),
+            Self::F64(n) => EuType::F64(*n).to_source(),
+            Self::Char(c) => EuType::Char(*c).to_source(),
+            Self::Str(s) => EuType::Str(s.clone()).to_source(),
+            Self::Vecz(bs) => format!("[{}]", Self::fmt_source(bs)),
+            Self::Map(bs) => format!("{{{}}}", Self::fmt_source(bs)),
+        }
+    }
+}
+
+impl<'eu> EuType<'eu> {
+    /// Renders a value as source that evaluates back to an equal value. A
+    /// `Seq` is collected and cut off at its first error.
+    #[must_use]
+    pub fn to_source(&self) -> String {
+        match self {
+            Self::Bool(true) => "True".into(),
+            Self::Bool(false) => "False".into(),
+            Self::I32(i32::MIN) => "MinI32".into(),
+            Self::I32(n) if n.is_negative() => format!("{}i32 _", n.unsigned_abs()),
+            Self::I32(n) => format!("{n}i32"),
+            Self::I64(i64::MIN) => "MinI64".into(),
+            Self::I64(n) if n.is_negative() => format!("{}i64 _", n.unsigned_abs()),
+            Self::I64(n) => format!("{n}i64"),
+            Self::IBig(n) if n.is_negative() => format!("{} _", n.unsigned_abs()),
+            Self::IBig(n) => n.to_string(),
+            Self::F64(n) if n.is_nan() => "NaN".into(),
+            Self::F64(n) if n.is_infinite() => {
+                if n.is_sign_negative() { "Inf _" } else { "Inf" }.into()
+            }
+            Self::F64(n) if n.is_sign_negative() => format!("{:?} _", -n.0),
+            Self::F64(n) => format!("{:?}", n.0),
+            Self::Char(c) => format!("'{}", c.escape_debug()),
+            Self::Str(s) => format!("{:?}", s.as_str()),
+            Self::Word(w, _) => w.to_string(),
+            Self::Opt(None) => "None".into(),
+            Self::Opt(Some(t)) => format!("{} Some", t.to_source()),
+            Self::Res(Ok(t)) => format!("{} Ok", t.to_source()),
+            Self::Res(Err(t)) => format!("{} Err", t.to_source()),
+            Self::Vec(ts) => format!("[{}]", ts.iter().map(Self::to_source).join(" ")),
+            Self::Map(kvs) => format!(
+                "{{{}}}",
+                kvs.iter()
+                    .map(|(k, v)| format!("[{} {}]", k.to_source(), v.to_source()))
+                    .join(" ")
+            ),
+            Self::Set(ts) => format!("[{}] >Set", ts.iter().map(Self::to_source).join(" ")),
+            Self::Expr(ts) => format!("({})", EuSyn::fmt_source(ts)),
+            Self::Seq(it) => format!(
+                "[{}] >Seq",
+                it.clone()
+                    .map_while(Result::ok)
+                    .map(|t| t.to_source())
+                    .join(" ")
+            ),
+        }
+    }
+
+    /// Reads a value written by [`Self::to_source`]. Only the words that
+    /// `to_source` emits are understood, so nothing is evaluated.
+    pub fn from_source(s: &str) -> EuRes<Self> {
+        let ts = euphrates
+            .parse(input(s, 0))
+            .map_err(|e| anyhow!(e.into_inner()))?;
+        match &*Self::read_syns(ts)? {
+            [t] => Ok(t.clone()),
+            ts => Err(anyhow!("expected 1 value, found {}", ts.len()).into()),
+        }
+    }
+
+    fn read_syns(ts: EcoVec<EuSyn<'eu>>) -> EuRes<EcoVec<Self>> {
+        let mut stack: EcoVec<Self> = EcoVec::new();
+        for t in ts {
+            let t = match t {
+                EuSyn::Raw(Self::Word(w, _), _) => {
+                    let mut arg = || {
+                        stack
+                            .pop()
+                            .with_context(|| format!("missing argument to `{w}`"))
+                    };
+                    match &*w {
+                        "True" => Self::Bool(true),
+                        "False" => Self::Bool(false),
+                        "MinI32" => Self::I32(i32::MIN),
+                        "MinI64" => Self::I64(i64::MIN),
+                        "Inf" => Self::F64(OrderedFloat::infinity()),
+                        "NaN" => Self::F64(OrderedFloat::nan()),
+                        "_" => (-arg()?)?,
+                        "None" => Self::Opt(None),
+                        "Some" => Self::opt(Some(arg()?)),
+                        "Ok" => Self::res(Ok(arg()?)),
+                        "Err" => Self::res(Err(arg()?)),
+                        ">Set" => Self::Set(arg()?.to_set()?),
+                        ">Seq" => Self::Seq(arg()?.to_seq()),
+                        _ => return Err(anyhow!("unexpected word `{w}`").into()),
+                    }
+                }
+                EuSyn::Raw(t, _) => t,
+                EuSyn::Vec(ts, _) => Self::Vec(Self::read_syns(ts)?),
+                EuSyn::Map(ts, _) => Self::Map(Rc::new(
+                    Self::read_syns(ts)?
+                        .into_iter()
+                        .map(Self::to_pair)
+                        .try_collect()?,
+                )),
+                t => return Err(anyhow!("unexpected `{t:?}`").into()),
+            };
+            stack.push(t);
+        }
+        Ok(stack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let t = EuType::vec([
+            EuType::Bool(true),
+            EuType::i32(-3),
+            EuType::I32(i32::MIN),
+            EuType::i64(4),
+            EuType::ibig(-5),
+            EuType::f64(-0.5),
+            EuType::f64(f64::NEG_INFINITY),
+            EuType::f64(f64::NAN),
+            EuType::char('\''),
+            EuType::str("a\"b\n\u{7f}"),
+            EuType::opt(None),
+            EuType::res(Err(EuType::opt(Some(EuType::str("e"))))),
+            EuType::map_([(EuType::str("k"), EuType::set([EuType::i32(1)]))]),
+            EuType::expr([EuSyn::var("x"), EuType::word("y").into()]),
+        ]);
+        let s = t.to_source();
+        assert_eq!(
+            s,
+            r#"[True 3i32 _ MinI32 4i64 5 _ 0.5 _ Inf _ NaN '\' "a\"b\n\u{7f}" None "e" Some Err {["k" [1i32] >Set]} ($x y)]"#
+        );
+        assert_eq!(EuType::from_source(&s).unwrap(), t);
+        assert!(EuType::from_source("1 foo").is_err());
+        assert!(EuType::from_source("1 2").is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{
+        Hash,
+        Hasher,
+    },
+};
+
+/// Byte range of a parsed node in source `src`, as registered with
+/// [`crate::env::EuEnvCtx::add_source`]. Source `0` is unregistered, e.g. code
+/// parsed from a string at runtime.
+///
+/// Spans are metadata only and always compare equal.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EuSpan {
+    pub src: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl EuSpan {
+    #[inline]
+    #[must_use]
+    pub fn new(src: u32, start: usize, end: usize) -> Self {
+        Self { src, start, end }
+    }
+}
+
+impl PartialEq for EuSpan {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for EuSpan {}
+
+impl PartialOrd for EuSpan {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EuSpan {
+    fn cmp(&self, _: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for EuSpan {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// A span resolved against its source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EuLoc {
+    pub line: usize,
+    pub col: usize,
+    pub len: usize,
+    pub text: String,
+}
+
+impl EuLoc {
+    #[must_use]
+    pub fn new(src: &str, span: EuSpan) -> Self {
+        let start = src.floor_char_boundary(span.start);
+        let end = src.floor_char_boundary(span.end.max(start));
+        let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = src[start..].find('\n').map_or(src.len(), |i| start + i);
+        Self {
+            line: src[..start].matches('\n').count() + 1,
+            col: src[line_start..start].chars().count() + 1,
+            len: src[start..end.min(line_end)].chars().count(),
+            text: src[line_start..line_end].into(),
+        }
+    }
+
+    #[must_use]
+    pub fn snippet(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        format!(
+            "at {self}\n{gutter} |\n{} | {}\n{gutter} | {}{}\n",
+            self.line,
+            self.text,
+            " ".repeat(self.col - 1),
+            "^".repeat(self.len.max(1)),
+        )
+    }
+}
+
+impl fmt::Display for EuLoc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loc() {
+        let src = "1 2 +\nfoo bar\n";
+        let loc = EuLoc::new(src, EuSpan::new(1, 10, 13));
+        assert_eq!((loc.line, loc.col, loc.len), (2, 5, 3));
+        assert_eq!(loc.text, "foo bar");
+        assert_eq!(
+            loc.snippet(),
+            "at line 2, column 5\n  |\n2 | foo bar\n  |     ^^^\n"
+        );
+        assert_eq!(EuLoc::new(src, EuSpan::new(1, 4, 5)).col, 5);
+    }
+}
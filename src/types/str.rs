@@ -0,0 +1,227 @@
+use anyhow::anyhow;
+use hipstr::LocalHipStr;
+use itertools::Itertools;
+use ordermap::OrderMap;
+use regex::Regex;
+
+use crate::types::{
+    EuRes,
+    EuType,
+};
+
+impl<'eu> EuType<'eu> {
+    #[must_use]
+    pub fn to_str(self) -> LocalHipStr<'eu> {
+        match self {
+            Self::Str(s) => s,
+            Self::Char(c) => c.to_string().into(),
+            _ => self.to_string().into(),
+        }
+    }
+
+    #[must_use]
+    pub fn split_str(self, sep: &str) -> Self {
+        let s = self.to_str();
+        if sep.is_empty() {
+            Self::Vec(s.chars().map(Self::char).collect())
+        } else {
+            Self::Vec(s.split(sep).map(Self::Str).collect())
+        }
+    }
+
+    #[must_use]
+    pub fn split_ws(self) -> Self {
+        Self::Vec(self.to_str().split_whitespace().map(Self::Str).collect())
+    }
+
+    #[must_use]
+    pub fn lines(self) -> Self {
+        Self::Vec(self.to_str().lines().map(Self::Str).collect())
+    }
+
+    pub fn join_str(self, sep: &str) -> EuRes<Self> {
+        let ts: Vec<_> = self.to_seq().map_ok(Self::to_str).try_collect()?;
+        Ok(Self::Str(LocalHipStr::join(ts, sep)))
+    }
+
+    #[must_use]
+    pub fn find_str(self, pat: &str) -> Self {
+        let s = self.to_str();
+        Self::opt(
+            s.find(pat)
+                .and_then(|i| s[..i].chars().count().try_into().ok())
+                .map(Self::I64),
+        )
+    }
+
+    pub fn pad_str(self, width: usize, fill: &str, start: bool) -> EuRes<Self> {
+        let s = self.to_str();
+        let len = s.chars().count();
+        if len >= width {
+            return Ok(Self::Str(s));
+        }
+        if fill.is_empty() {
+            return Err(anyhow!("cannot pad with empty fill").into());
+        }
+        let n = width - len;
+        let fills = fill.chars().count();
+        let mut out = try_string(
+            (n / fills)
+                .checked_mul(fill.len())
+                .and_then(|b| b.checked_add(fill.chars().take(n % fills).map(char::len_utf8).sum()))
+                .and_then(|b| b.checked_add(s.len())),
+        )?;
+        if !start {
+            out.push_str(&s);
+        }
+        out.extend(fill.chars().cycle().take(n));
+        if start {
+            out.push_str(&s);
+        }
+        Ok(Self::str(out))
+    }
+
+    pub fn repeat_str(self, n: usize) -> EuRes<Self> {
+        let s = self.to_str();
+        let mut out = try_string(s.len().checked_mul(n))?;
+        for _ in 0..n {
+            out.push_str(&s);
+        }
+        Ok(Self::str(out))
+    }
+
+    #[must_use]
+    pub fn re_find(self, re: &Regex) -> Self {
+        let s = self.to_str();
+        Self::opt(re.find(&s).map(|m| Self::Str(s.slice_ref(m.as_str()))))
+    }
+
+    #[must_use]
+    pub fn re_find_all(self, re: &Regex) -> Self {
+        let s = self.to_str();
+        Self::Vec(
+            re.find_iter(&s)
+                .map(|m| Self::Str(s.slice_ref(m.as_str())))
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    pub fn re_captures(self, re: &Regex) -> Self {
+        let s = self.to_str();
+        Self::opt(re.captures(&s).map(|caps| {
+            Self::map_(
+                re.capture_names()
+                    .zip(caps.iter())
+                    .enumerate()
+                    .filter_map(|(i, (name, m))| {
+                        let k = name.map_or_else(|| Self::ibig(i), |w| Self::str(w.to_owned()));
+                        m.map(|m| (k, Self::Str(s.slice_ref(m.as_str()))))
+                    })
+                    .collect::<OrderMap<_, _>>(),
+            )
+        }))
+    }
+
+    #[must_use]
+    pub fn re_replace(self, re: &Regex, rep: &str) -> Self {
+        Self::str(re.replace_all(&self.to_str(), rep).into_owned())
+    }
+
+    #[must_use]
+    pub fn re_split(self, re: &Regex) -> Self {
+        let s = self.to_str();
+        Self::Vec(re.split(&s).map(|p| Self::Str(s.slice_ref(p))).collect())
+    }
+}
+
+/// Allocates room for `bytes`, failing instead of aborting when that is more
+/// than can be allocated.
+pub(super) fn try_string(bytes: Option<usize>) -> EuRes<String> {
+    let mut s = String::new();
+    bytes
+        .and_then(|n| s.try_reserve_exact(n).ok())
+        .ok_or_else(|| anyhow!("string too long to allocate"))?;
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_join() {
+        let t = EuType::str("a,b,,c").split_str(",");
+        assert_eq!(
+            t,
+            EuType::vec([
+                EuType::str("a"),
+                EuType::str("b"),
+                EuType::str(""),
+                EuType::str("c")
+            ])
+        );
+        assert_eq!(t.join_str("-").unwrap(), EuType::str("a-b--c"));
+        assert_eq!(
+            EuType::str("ab").split_str(""),
+            EuType::vec([EuType::char('a'), EuType::char('b')])
+        );
+    }
+
+    #[test]
+    fn find() {
+        assert_eq!(
+            EuType::str("αβγ").find_str("γ"),
+            EuType::opt(Some(EuType::i64(2)))
+        );
+        assert_eq!(EuType::str("abc").find_str("x"), EuType::Opt(None));
+    }
+
+    #[test]
+    fn pad() {
+        assert_eq!(
+            EuType::str("7").pad_str(3, "0", true).unwrap(),
+            EuType::str("007")
+        );
+        assert_eq!(
+            EuType::str("ab").pad_str(5, "-=", false).unwrap(),
+            EuType::str("ab-=-")
+        );
+        assert_eq!(
+            EuType::str("abc").pad_str(2, " ", true).unwrap(),
+            EuType::str("abc")
+        );
+        assert_eq!(
+            EuType::str("x").pad_str(4, "αβ", true).unwrap(),
+            EuType::str("αβαx")
+        );
+        assert!(EuType::str("a").pad_str(2, "", true).is_err());
+        assert!(EuType::str("a").pad_str(usize::MAX, "ab", true).is_err());
+        assert!(EuType::str("a").pad_str(1 << 62, "ab", false).is_err());
+    }
+
+    #[test]
+    fn repeat() {
+        assert_eq!(
+            EuType::str("ab").repeat_str(3).unwrap(),
+            EuType::str("ababab")
+        );
+        assert_eq!(EuType::str("ab").repeat_str(0).unwrap(), EuType::str(""));
+        assert!(EuType::str("ab").repeat_str(usize::MAX).is_err());
+        assert!(EuType::str("ab").repeat_str(1 << 62).is_err());
+    }
+
+    #[test]
+    fn re_captures() {
+        let re = Regex::new(r"(?<k>\w+)=(\d+)").unwrap();
+        assert_eq!(
+            EuType::str("x: a=1").re_captures(&re),
+            EuType::opt(Some(EuType::map_([
+                (EuType::ibig(0), EuType::str("a=1")),
+                (EuType::str("k"), EuType::str("a")),
+                (EuType::ibig(2), EuType::str("1")),
+            ])))
+        );
+        assert_eq!(EuType::str("nope").re_captures(&re), EuType::Opt(None));
+    }
+}
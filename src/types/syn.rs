@@ -12,38 +12,92 @@ use itertools::Itertools;
 
 use crate::types::{
     EuBind,
+    EuSpan,
     EuType,
 };
 
 #[derive(Debug, Display, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, IsVariant)]
 pub enum EuSyn<'eu> {
     #[debug("{_0:?}")]
-    Raw(EuType<'eu>),
+    #[display("{_0}")]
+    Raw(EuType<'eu>, EuSpan),
     #[debug("${_0}")]
-    Var(LocalHipStr<'eu>),
+    #[display("{_0}")]
+    Var(LocalHipStr<'eu>, EuSpan),
     #[debug("\\{_0}")]
-    Move(LocalHipStr<'eu>),
+    #[display("{_0}")]
+    Move(LocalHipStr<'eu>, EuSpan),
     #[debug(".{_0}")]
-    Get(LocalHipStr<'eu>),
+    #[display("{_0}")]
+    Get(LocalHipStr<'eu>, EuSpan),
     #[debug("$Vec({})", _0.iter().map(|t| format!("{t:?}")).join(" "))]
     #[display("{}", _0.iter().join(" "))]
-    Vec(EcoVec<Self>),
+    Vec(EcoVec<Self>, EuSpan),
     #[debug("$Map({})", _0.iter().map(|t| format!("{t:?}")).join(" "))]
     #[display("{}", _0.iter().join(" "))]
-    Map(EcoVec<Self>),
+    Map(EcoVec<Self>, EuSpan),
     #[debug("\\[{}]", _0.iter().map(|t| format!("{t:?}")).join(" "))]
     #[display("{}", _0.iter().join(" "))]
-    Bind(EcoVec<EuBind<'eu>>),
+    Bind(EcoVec<EuBind<'eu>>, EuSpan),
+}
+
+impl<'eu> EuSyn<'eu> {
+    #[inline]
+    pub fn var(w: impl Into<LocalHipStr<'eu>>) -> Self {
+        Self::Var(w.into(), EuSpan::default())
+    }
+
+    #[inline]
+    pub fn r#move(w: impl Into<LocalHipStr<'eu>>) -> Self {
+        Self::Move(w.into(), EuSpan::default())
+    }
+
+    #[inline]
+    pub fn get(w: impl Into<LocalHipStr<'eu>>) -> Self {
+        Self::Get(w.into(), EuSpan::default())
+    }
+
+    #[inline]
+    pub fn vec(ts: impl Into<EcoVec<Self>>) -> Self {
+        Self::Vec(ts.into(), EuSpan::default())
+    }
+
+    #[inline]
+    pub fn map(ts: impl Into<EcoVec<Self>>) -> Self {
+        Self::Map(ts.into(), EuSpan::default())
+    }
+
+    #[inline]
+    pub fn bind(bs: impl Into<EcoVec<EuBind<'eu>>>) -> Self {
+        Self::Bind(bs.into(), EuSpan::default())
+    }
+
+    #[must_use]
+    pub fn span(&self) -> EuSpan {
+        match self {
+            Self::Raw(_, sp)
+            | Self::Var(_, sp)
+            | Self::Move(_, sp)
+            | Self::Get(_, sp)
+            | Self::Vec(_, sp)
+            | Self::Map(_, sp)
+            | Self::Bind(_, sp) => *sp,
+        }
+    }
 }
 
 impl<'eu> From<EuType<'eu>> for EuSyn<'eu> {
     fn from(value: EuType<'eu>) -> Self {
-        Self::Raw(value)
+        let sp = match value {
+            EuType::Word(_, sp) => sp,
+            _ => EuSpan::default(),
+        };
+        Self::Raw(value, sp)
     }
 }
 
 impl<'eu> From<EuBind<'eu>> for EuSyn<'eu> {
     fn from(value: EuBind<'eu>) -> Self {
-        Self::Bind(eco_vec![value])
+        Self::bind(eco_vec![value])
     }
 }
@@ -224,7 +224,7 @@ impl EuType<'_> {
                 Ok(self)
             }
             Self::Expr(ref mut ts) => {
-                ts.push(EuSyn::Raw(t));
+                ts.push(t.into());
                 Ok(self)
             }
             Self::Str(ref mut s) => {
@@ -291,7 +291,7 @@ impl EuType<'_> {
                 }
             }
             Self::Expr(ref mut ts) => {
-                ts.insert(check(ts.len())?, EuSyn::Raw(t));
+                ts.insert(check(ts.len())?, t.into());
                 Ok(self)
             }
             Self::Str(s) => {